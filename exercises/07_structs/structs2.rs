@@ -1,10 +1,16 @@
+// The channel through which an order was made.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Channel {
+    Phone,
+    Mobile,
+    Email,
+}
+
 #[derive(Debug)]
 struct Order {
     name: String,
     year: u32,
-    made_by_phone: bool,
-    made_by_mobile: bool,
-    made_by_email: bool,
+    channel: Channel,
     item_number: u32,
     count: u32,
 }
@@ -13,9 +19,7 @@ fn create_order_template() -> Order {
     Order {
         name: String::from("Bob"),
         year: 2019,
-        made_by_phone: false,
-        made_by_mobile: false,
-        made_by_email: true,
+        channel: Channel::Email,
         item_number: 123,
         count: 0,
     }
@@ -34,6 +38,7 @@ mod tests {
         let order_template = create_order_template();
 
         // TODO: Create your own order using the update syntax and template above!
+        // Only the name and the count differ from the template.
         let your_order = Order {
             name: String::from("Hacker in Rust"),
             count: 1,
@@ -42,9 +47,7 @@ mod tests {
 
         assert_eq!(your_order.name, "Hacker in Rust");
         assert_eq!(your_order.year, order_template.year);
-        assert_eq!(your_order.made_by_phone, order_template.made_by_phone);
-        assert_eq!(your_order.made_by_mobile, order_template.made_by_mobile);
-        assert_eq!(your_order.made_by_email, order_template.made_by_email);
+        assert_eq!(your_order.channel, order_template.channel);
         assert_eq!(your_order.item_number, order_template.item_number);
         assert_eq!(your_order.count, 1);
     }
@@ -1,10 +1,20 @@
-#[derive(Debug)]
+use std::collections::{BTreeMap, HashMap};
+
+// The channel through which an order was made. Using an enum instead of one
+// `bool` per channel makes it impossible to have an order made through no
+// channel or through multiple channels.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Channel {
+    Phone,
+    Mobile,
+    Email,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
 struct Order {
     name: String,
     year: u32,
-    made_by_phone: bool,
-    made_by_mobile: bool,
-    made_by_email: bool,
+    channel: Channel,
     item_number: u32,
     count: u32,
 }
@@ -13,14 +23,167 @@ fn create_order_template() -> Order {
     Order {
         name: String::from("Bob"),
         year: 2019,
-        made_by_phone: false,
-        made_by_mobile: false,
-        made_by_email: true,
+        channel: Channel::Email,
         item_number: 123,
         count: 0,
     }
 }
 
+#[derive(Debug, PartialEq, Eq)]
+enum OrderError {
+    MissingField(&'static str),
+    EmptyName,
+    ImplausibleYear(u32),
+    ZeroCount,
+}
+
+// Builds an `Order` step by step and validates it in `build`.
+#[derive(Default)]
+struct OrderBuilder {
+    name: Option<String>,
+    year: Option<u32>,
+    channel: Option<Channel>,
+    item_number: Option<u32>,
+    count: Option<u32>,
+}
+
+impl OrderBuilder {
+    // The range of years in which an order could have been made.
+    const YEARS: std::ops::RangeInclusive<u32> = 1970..=2100;
+
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    fn year(mut self, year: u32) -> Self {
+        self.year = Some(year);
+        self
+    }
+
+    fn channel(mut self, channel: Channel) -> Self {
+        self.channel = Some(channel);
+        self
+    }
+
+    fn item_number(mut self, item_number: u32) -> Self {
+        self.item_number = Some(item_number);
+        self
+    }
+
+    fn count(mut self, count: u32) -> Self {
+        self.count = Some(count);
+        self
+    }
+
+    fn build(self) -> Result<Order, OrderError> {
+        let name = self.name.ok_or(OrderError::MissingField("name"))?;
+        let year = self.year.ok_or(OrderError::MissingField("year"))?;
+        let channel = self.channel.ok_or(OrderError::MissingField("channel"))?;
+        let item_number = self
+            .item_number
+            .ok_or(OrderError::MissingField("item_number"))?;
+        let count = self.count.ok_or(OrderError::MissingField("count"))?;
+
+        if name.trim().is_empty() {
+            return Err(OrderError::EmptyName);
+        }
+        if !Self::YEARS.contains(&year) {
+            return Err(OrderError::ImplausibleYear(year));
+        }
+        if count == 0 {
+            return Err(OrderError::ZeroCount);
+        }
+
+        Ok(Order {
+            name,
+            year,
+            channel,
+            item_number,
+            count,
+        })
+    }
+}
+
+// Starts from an existing order, similar to the struct update syntax.
+impl From<Order> for OrderBuilder {
+    fn from(order: Order) -> Self {
+        Self {
+            name: Some(order.name),
+            year: Some(order.year),
+            channel: Some(order.channel),
+            item_number: Some(order.item_number),
+            count: Some(order.count),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct OrderId(u64);
+
+// Stores orders under increasing ids.
+#[derive(Default)]
+struct OrderLedger {
+    next_id: u64,
+    orders: BTreeMap<OrderId, Order>,
+}
+
+impl OrderLedger {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn add(&mut self, order: Order) -> OrderId {
+        let id = OrderId(self.next_id);
+        self.next_id += 1;
+        self.orders.insert(id, order);
+        id
+    }
+
+    fn get(&self, id: OrderId) -> Option<&Order> {
+        self.orders.get(&id)
+    }
+
+    // Ids of removed orders aren't reused.
+    fn remove(&mut self, id: OrderId) -> Option<Order> {
+        self.orders.remove(&id)
+    }
+
+    fn len(&self) -> usize {
+        self.orders.len()
+    }
+
+    // The total count of ordered items per item number.
+    fn totals_per_item(&self) -> HashMap<u32, u64> {
+        let mut totals = HashMap::new();
+        for order in self.orders.values() {
+            *totals.entry(order.item_number).or_default() += u64::from(order.count);
+        }
+        totals
+    }
+
+    // The number of orders made through each channel.
+    fn orders_per_channel(&self) -> HashMap<Channel, usize> {
+        let mut counts = HashMap::new();
+        for order in self.orders.values() {
+            *counts.entry(order.channel).or_default() += 1;
+        }
+        counts
+    }
+
+    // The orders made through `channel` sorted by id.
+    fn orders_by_channel(&self, channel: Channel) -> impl Iterator<Item = (OrderId, &Order)> {
+        self.orders
+            .iter()
+            .filter(move |(_, order)| order.channel == channel)
+            .map(|(id, order)| (*id, order))
+    }
+}
+
 fn main() {
     // You can optionally experiment here.
 }
@@ -42,10 +205,141 @@ mod tests {
 
         assert_eq!(your_order.name, "Hacker in Rust");
         assert_eq!(your_order.year, order_template.year);
-        assert_eq!(your_order.made_by_phone, order_template.made_by_phone);
-        assert_eq!(your_order.made_by_mobile, order_template.made_by_mobile);
-        assert_eq!(your_order.made_by_email, order_template.made_by_email);
+        assert_eq!(your_order.channel, order_template.channel);
         assert_eq!(your_order.item_number, order_template.item_number);
         assert_eq!(your_order.count, 1);
     }
+
+    #[test]
+    fn build_order() {
+        let order = OrderBuilder::new()
+            .name("Alice")
+            .year(2024)
+            .channel(Channel::Phone)
+            .item_number(7)
+            .count(3)
+            .build();
+
+        assert_eq!(
+            order,
+            Ok(Order {
+                name: String::from("Alice"),
+                year: 2024,
+                channel: Channel::Phone,
+                item_number: 7,
+                count: 3,
+            }),
+        );
+    }
+
+    #[test]
+    fn build_order_from_template() {
+        let order = OrderBuilder::from(create_order_template())
+            .name("Hacker in Rust")
+            .count(1)
+            .build()
+            .unwrap();
+
+        assert_eq!(order.name, "Hacker in Rust");
+        assert_eq!(order.year, 2019);
+        assert_eq!(order.channel, Channel::Email);
+        assert_eq!(order.count, 1);
+
+        // The template itself has a count of 0.
+        assert_eq!(
+            OrderBuilder::from(create_order_template()).build(),
+            Err(OrderError::ZeroCount),
+        );
+    }
+
+    #[test]
+    fn invalid_orders() {
+        let builder = || OrderBuilder::from(create_order_template()).count(1);
+
+        assert_eq!(builder().name("").build(), Err(OrderError::EmptyName));
+        assert_eq!(builder().name("  ").build(), Err(OrderError::EmptyName));
+        assert_eq!(
+            builder().year(1969).build(),
+            Err(OrderError::ImplausibleYear(1969)),
+        );
+        assert_eq!(
+            builder().year(2101).build(),
+            Err(OrderError::ImplausibleYear(2101)),
+        );
+        assert!(builder().year(1970).build().is_ok());
+        assert_eq!(builder().count(0).build(), Err(OrderError::ZeroCount));
+    }
+
+    #[test]
+    fn missing_fields() {
+        assert_eq!(
+            OrderBuilder::new().build(),
+            Err(OrderError::MissingField("name")),
+        );
+        assert_eq!(
+            OrderBuilder::new()
+                .name("Alice")
+                .year(2024)
+                .channel(Channel::Mobile)
+                .count(1)
+                .build(),
+            Err(OrderError::MissingField("item_number")),
+        );
+    }
+
+    fn order(item_number: u32, count: u32, channel: Channel) -> Order {
+        OrderBuilder::from(create_order_template())
+            .item_number(item_number)
+            .count(count)
+            .channel(channel)
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn ledger_ids() {
+        let mut ledger = OrderLedger::new();
+        let first = ledger.add(order(1, 1, Channel::Phone));
+        let second = ledger.add(order(2, 1, Channel::Email));
+        assert_ne!(first, second);
+        assert_eq!(ledger.len(), 2);
+        assert_eq!(ledger.get(second).unwrap().item_number, 2);
+
+        assert_eq!(ledger.remove(first).unwrap().item_number, 1);
+        assert!(ledger.get(first).is_none());
+        assert!(ledger.remove(first).is_none());
+
+        // Ids aren't reused after removing an order.
+        let third = ledger.add(order(3, 1, Channel::Phone));
+        assert!(third > second);
+        assert_eq!(ledger.len(), 2);
+    }
+
+    #[test]
+    fn ledger_queries() {
+        let mut ledger = OrderLedger::new();
+        ledger.add(order(1, 2, Channel::Phone));
+        let email = ledger.add(order(2, 5, Channel::Email));
+        ledger.add(order(1, 3, Channel::Mobile));
+        ledger.add(order(1, u32::MAX, Channel::Phone));
+
+        let totals = ledger.totals_per_item();
+        assert_eq!(totals.len(), 2);
+        assert_eq!(totals[&1], 5 + u64::from(u32::MAX));
+        assert_eq!(totals[&2], 5);
+
+        let per_channel = ledger.orders_per_channel();
+        assert_eq!(per_channel[&Channel::Phone], 2);
+        assert_eq!(per_channel[&Channel::Email], 1);
+        assert_eq!(per_channel[&Channel::Mobile], 1);
+
+        let emails: Vec<OrderId> = ledger
+            .orders_by_channel(Channel::Email)
+            .map(|(id, _)| id)
+            .collect();
+        assert_eq!(emails, [email]);
+        assert_eq!(ledger.orders_by_channel(Channel::Phone).count(), 2);
+
+        assert!(OrderLedger::new().orders_per_channel().is_empty());
+    }
 }
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 #[derive(Debug)]
 struct Package {
     sender_country: String,
@@ -5,7 +7,81 @@ struct Package {
     weight_in_grams: u32,
 }
 
+// The countries that `Package::try_new` accepts as (code, name) pairs.
+const COUNTRIES: [(&str, &str); 20] = [
+    ("AT", "Austria"),
+    ("AU", "Australia"),
+    ("BR", "Brazil"),
+    ("CA", "Canada"),
+    ("CH", "Switzerland"),
+    ("CN", "China"),
+    ("DE", "Germany"),
+    ("ES", "Spain"),
+    ("FR", "France"),
+    ("GB", "United Kingdom"),
+    ("IN", "India"),
+    ("IT", "Italy"),
+    ("JP", "Japan"),
+    ("MX", "Mexico"),
+    ("NL", "Netherlands"),
+    ("PL", "Poland"),
+    ("PT", "Portugal"),
+    ("RU", "Russia"),
+    ("SE", "Sweden"),
+    ("US", "United States"),
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Party {
+    Sender,
+    Recipient,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum PackageError {
+    TooLight { weight_in_grams: u32 },
+    TooHeavy { weight_in_grams: u32 },
+    EmptyCountry(Party),
+    UnknownCountry(Party, String),
+}
+
 impl Package {
+    const MIN_WEIGHT_IN_GRAMS: u32 = 10;
+    const MAX_WEIGHT_IN_GRAMS: u32 = 30_000;
+
+    // Like `new`, but returns an error instead of panicking. A country can be
+    // given by its name or by its code. Codes are replaced by the name.
+    fn try_new(
+        sender_country: String,
+        recipient_country: String,
+        weight_in_grams: u32,
+    ) -> Result<Self, PackageError> {
+        if weight_in_grams < Self::MIN_WEIGHT_IN_GRAMS {
+            return Err(PackageError::TooLight { weight_in_grams });
+        }
+        if weight_in_grams > Self::MAX_WEIGHT_IN_GRAMS {
+            return Err(PackageError::TooHeavy { weight_in_grams });
+        }
+
+        Ok(Self {
+            sender_country: Self::country_name(sender_country, Party::Sender)?,
+            recipient_country: Self::country_name(recipient_country, Party::Recipient)?,
+            weight_in_grams,
+        })
+    }
+
+    fn country_name(country: String, party: Party) -> Result<String, PackageError> {
+        if country.trim().is_empty() {
+            return Err(PackageError::EmptyCountry(party));
+        }
+
+        COUNTRIES
+            .iter()
+            .find(|(code, name)| country == *code || country == *name)
+            .map(|(_, name)| name.to_string())
+            .ok_or(PackageError::UnknownCountry(party, country))
+    }
+
     fn new(sender_country: String, recipient_country: String, weight_in_grams: u32) -> Self {
         if weight_in_grams < 10 {
             // This isn't how you should handle errors in Rust, but we will
@@ -29,6 +105,309 @@ impl Package {
         //                                  ^^^^^^ added
         self.weight_in_grams * cents_per_gram
     }
+
+    fn route(&self) -> Route {
+        Route {
+            sender_country: self.sender_country.clone(),
+            recipient_country: self.recipient_country.clone(),
+        }
+    }
+
+    fn zone(&self) -> Zone {
+        if self.is_international() {
+            Zone::International
+        } else {
+            Zone::Domestic
+        }
+    }
+}
+
+// An amount of money in cents. All arithmetic on it is checked and returns
+// `None` on overflow.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+struct Cents(u32);
+
+impl Cents {
+    fn checked_add(self, other: Self) -> Option<Self> {
+        self.0.checked_add(other.0).map(Self)
+    }
+
+    fn checked_mul(self, factor: u32) -> Option<Self> {
+        self.0.checked_mul(factor).map(Self)
+    }
+
+    // The given percentage of the amount rounded down to whole cents.
+    // The product is computed in `u64` where it can't overflow, so only a
+    // result that doesn't fit into `u32` is an overflow.
+    fn checked_percent(self, percent: u32) -> Option<Self> {
+        let amount = u64::from(self.0) * u64::from(percent) / 100;
+        u32::try_from(amount).ok().map(Self)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Zone {
+    Domestic,
+    International,
+}
+
+// The rate for packages weighing up to `max_weight_in_grams` (inclusive):
+// `base_fee + weight_in_grams * cents_per_gram`.
+#[derive(Clone, Debug)]
+struct WeightBracket {
+    max_weight_in_grams: u32,
+    base_fee: Cents,
+    cents_per_gram: u32,
+}
+
+// A surcharge of `percent` of the fee plus a flat amount.
+#[derive(Clone, Debug, Default)]
+struct Surcharge {
+    percent: u32,
+    flat: Cents,
+}
+
+#[derive(Clone, Debug)]
+struct ZoneRates {
+    // Sorted by `max_weight_in_grams`.
+    brackets: Vec<WeightBracket>,
+    surcharge: Surcharge,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum RateError {
+    // The package is heavier than the largest weight bracket.
+    NoWeightBracket { weight_in_grams: u32 },
+    Overflow,
+}
+
+// The fees of a package broken down into their parts.
+#[derive(Debug, PartialEq, Eq)]
+struct Quote {
+    base: Cents,
+    surcharge: Cents,
+    // Added to reach the minimum fee.
+    minimum_fee_top_up: Cents,
+    total: Cents,
+}
+
+#[derive(Clone, Debug)]
+struct RateTable {
+    domestic: ZoneRates,
+    international: ZoneRates,
+    minimum_fee: Cents,
+}
+
+impl RateTable {
+    fn standard() -> Self {
+        let bracket = |max_weight_in_grams, base_fee, cents_per_gram| WeightBracket {
+            max_weight_in_grams,
+            base_fee: Cents(base_fee),
+            cents_per_gram,
+        };
+
+        Self {
+            domestic: ZoneRates {
+                brackets: vec![
+                    bracket(1_000, 0, 3),
+                    bracket(10_000, 1_000, 2),
+                    bracket(u32::MAX, 11_000, 1),
+                ],
+                surcharge: Surcharge::default(),
+            },
+            international: ZoneRates {
+                brackets: vec![
+                    bracket(1_000, 0, 5),
+                    bracket(10_000, 2_000, 3),
+                    bracket(u32::MAX, 22_000, 1),
+                ],
+                surcharge: Surcharge {
+                    percent: 10,
+                    flat: Cents(250),
+                },
+            },
+            minimum_fee: Cents(500),
+        }
+    }
+
+    fn zone_rates(&self, zone: Zone) -> &ZoneRates {
+        match zone {
+            Zone::Domestic => &self.domestic,
+            Zone::International => &self.international,
+        }
+    }
+
+    fn quote(&self, package: &Package) -> Result<Quote, RateError> {
+        let weight_in_grams = package.weight_in_grams;
+        let rates = self.zone_rates(package.zone());
+
+        let bracket = rates
+            .brackets
+            .iter()
+            .find(|bracket| weight_in_grams <= bracket.max_weight_in_grams)
+            .ok_or(RateError::NoWeightBracket { weight_in_grams })?;
+
+        let base = Cents(weight_in_grams)
+            .checked_mul(bracket.cents_per_gram)
+            .and_then(|fee| fee.checked_add(bracket.base_fee))
+            .ok_or(RateError::Overflow)?;
+        let surcharge = base
+            .checked_percent(rates.surcharge.percent)
+            .and_then(|fee| fee.checked_add(rates.surcharge.flat))
+            .ok_or(RateError::Overflow)?;
+        let fee = base.checked_add(surcharge).ok_or(RateError::Overflow)?;
+
+        let minimum_fee_top_up = Cents(self.minimum_fee.0.saturating_sub(fee.0));
+        let total = fee.max(self.minimum_fee);
+
+        Ok(Quote {
+            base,
+            surcharge,
+            minimum_fee_top_up,
+            total,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Route {
+    sender_country: String,
+    recipient_country: String,
+}
+
+// How packages on the same route are consolidated into boxes.
+#[derive(Clone, Debug)]
+struct ConsolidationPolicy {
+    max_box_weight_in_grams: u32,
+    // The discount on the fee of a box for each package after the first one.
+    discount_percent_per_extra_package: u32,
+    max_discount_percent: u32,
+}
+
+impl ConsolidationPolicy {
+    // Never more than 100%, even if `max_discount_percent` is higher.
+    fn discount_percent(&self, n_packages: usize) -> u32 {
+        let n_extra_packages = u32::try_from(n_packages.saturating_sub(1)).unwrap_or(u32::MAX);
+        n_extra_packages
+            .saturating_mul(self.discount_percent_per_extra_package)
+            .min(self.max_discount_percent)
+            .min(100)
+    }
+}
+
+// One box of the shipment. It contains the packages with the given indexes.
+#[derive(Debug, PartialEq, Eq)]
+struct InvoiceLine {
+    route: Route,
+    packages: Vec<usize>,
+    weight_in_grams: u32,
+    quote: Quote,
+    discount: Cents,
+    fee: Cents,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Invoice {
+    lines: Vec<InvoiceLine>,
+    total: Cents,
+}
+
+// Several packages shipped at once.
+#[derive(Debug, Default)]
+struct Shipment {
+    packages: Vec<Package>,
+}
+
+impl Shipment {
+    fn new() -> Self {
+        Self::default()
+    }
+
+    fn add(&mut self, package: Package) {
+        self.packages.push(package);
+    }
+
+    // The indexes of the packages grouped by route.
+    fn routes(&self) -> BTreeMap<Route, Vec<usize>> {
+        let mut routes = BTreeMap::<Route, Vec<usize>>::new();
+        for (ind, package) in self.packages.iter().enumerate() {
+            routes.entry(package.route()).or_default().push(ind);
+        }
+        routes
+    }
+
+    // Splits the packages of one route into boxes that don't exceed the
+    // weight limit. The heaviest packages are packed first, each into the first
+    // box where it still fits. A package that is heavier than the limit gets a
+    // box of its own.
+    fn pack(
+        &self,
+        mut packages: Vec<usize>,
+        max_box_weight_in_grams: u32,
+    ) -> Vec<(Vec<usize>, u32)> {
+        packages.sort_by_key(|&ind| std::cmp::Reverse(self.packages[ind].weight_in_grams));
+
+        let mut boxes: Vec<(Vec<usize>, u32)> = Vec::new();
+        for ind in packages {
+            let weight_in_grams = self.packages[ind].weight_in_grams;
+            let free_box = boxes.iter_mut().find(|(_, box_weight)| {
+                box_weight
+                    .checked_add(weight_in_grams)
+                    .is_some_and(|weight| weight <= max_box_weight_in_grams)
+            });
+
+            match free_box {
+                Some((box_packages, box_weight)) => {
+                    box_packages.push(ind);
+                    *box_weight += weight_in_grams;
+                }
+                None => boxes.push((vec![ind], weight_in_grams)),
+            }
+        }
+
+        for (box_packages, _) in &mut boxes {
+            box_packages.sort_unstable();
+        }
+        boxes
+    }
+
+    fn invoice(
+        &self,
+        rate_table: &RateTable,
+        policy: &ConsolidationPolicy,
+    ) -> Result<Invoice, RateError> {
+        let mut lines = Vec::new();
+        let mut total = Cents(0);
+
+        for (route, packages) in self.routes() {
+            for (packages, weight_in_grams) in self.pack(packages, policy.max_box_weight_in_grams) {
+                // A box is quoted like a single package with the total weight.
+                let consolidated = Package {
+                    sender_country: route.sender_country.clone(),
+                    recipient_country: route.recipient_country.clone(),
+                    weight_in_grams,
+                };
+                let quote = rate_table.quote(&consolidated)?;
+                let discount = quote
+                    .total
+                    .checked_percent(policy.discount_percent(packages.len()))
+                    .ok_or(RateError::Overflow)?;
+                let fee = Cents(quote.total.0 - discount.0);
+                total = total.checked_add(fee).ok_or(RateError::Overflow)?;
+
+                lines.push(InvoiceLine {
+                    route: route.clone(),
+                    packages,
+                    weight_in_grams,
+                    quote,
+                    discount,
+                    fee,
+                });
+            }
+        }
+
+        Ok(Invoice { lines, total })
+    }
 }
 
 fn main() {
@@ -80,4 +459,380 @@ mod tests {
         assert_eq!(package.get_fees(cents_per_gram), 4500);
         assert_eq!(package.get_fees(cents_per_gram * 2), 9000);
     }
+
+    fn quote(sender_country: &str, recipient_country: &str, weight_in_grams: u32) -> Quote {
+        let package = Package::new(
+            sender_country.to_string(),
+            recipient_country.to_string(),
+            weight_in_grams,
+        );
+        RateTable::standard().quote(&package).unwrap()
+    }
+
+    #[test]
+    fn domestic_rates() {
+        // Spain to Spain as in `calculate_transport_fees`.
+        assert_eq!(
+            quote("Spain", "Spain", 1500),
+            Quote {
+                base: Cents(1_000 + 1500 * 2),
+                surcharge: Cents(0),
+                minimum_fee_top_up: Cents(0),
+                total: Cents(4_000),
+            },
+        );
+        assert_eq!(quote("Canada", "Canada", 1200).total, Cents(3_400));
+        // The brackets are continuous.
+        assert_eq!(quote("Canada", "Canada", 1000).total, Cents(3_000));
+        assert_eq!(quote("Canada", "Canada", 1001).total, Cents(3_002));
+        assert_eq!(quote("Canada", "Canada", 20_000).total, Cents(31_000));
+    }
+
+    #[test]
+    fn international_rates() {
+        let expected = Quote {
+            base: Cents(2_000 + 1200 * 3),
+            // 10% of the base fee plus 2.50.
+            surcharge: Cents(560 + 250),
+            minimum_fee_top_up: Cents(0),
+            total: Cents(6_410),
+        };
+        assert_eq!(quote("Spain", "Russia", 1200), expected);
+        assert_eq!(quote("Spain", "Austria", 1200), expected);
+        assert_eq!(
+            quote("Canada", "Spain", 500).total,
+            Cents(2_500 + 250 + 250)
+        );
+    }
+
+    #[test]
+    fn minimum_fee() {
+        assert_eq!(
+            quote("Spain", "Spain", 10),
+            Quote {
+                base: Cents(30),
+                surcharge: Cents(0),
+                minimum_fee_top_up: Cents(470),
+                total: Cents(500),
+            },
+        );
+        // The surcharge counts towards the minimum fee.
+        assert_eq!(quote("Spain", "Austria", 10).minimum_fee_top_up, Cents(195));
+        assert_eq!(quote("Spain", "Austria", 10).total, Cents(500));
+    }
+
+    #[test]
+    fn no_weight_bracket() {
+        let mut rate_table = RateTable::standard();
+        rate_table.domestic.brackets.pop();
+
+        let package = Package::new(String::from("Spain"), String::from("Spain"), 10_001);
+        assert_eq!(
+            rate_table.quote(&package),
+            Err(RateError::NoWeightBracket {
+                weight_in_grams: 10_001,
+            }),
+        );
+    }
+
+    #[test]
+    fn overflow() {
+        let heavy = |recipient_country: &str| {
+            Package::new(
+                String::from("Spain"),
+                recipient_country.to_string(),
+                u32::MAX,
+            )
+        };
+
+        // The heaviest possible package overflows with the standard rates.
+        let rate_table = RateTable::standard();
+        assert_eq!(
+            rate_table.quote(&heavy("Austria")),
+            Err(RateError::Overflow)
+        );
+        assert_eq!(rate_table.quote(&heavy("Spain")), Err(RateError::Overflow));
+
+        let mut rate_table = RateTable::standard();
+        rate_table.domestic.brackets[2].base_fee = Cents(0);
+        assert_eq!(
+            rate_table.quote(&heavy("Spain")).unwrap().total,
+            Cents(u32::MAX),
+        );
+
+        rate_table.domestic.surcharge.flat = Cents(1);
+        assert_eq!(rate_table.quote(&heavy("Spain")), Err(RateError::Overflow));
+    }
+
+    #[test]
+    fn checked_cents() {
+        assert_eq!(Cents(10).checked_add(Cents(5)), Some(Cents(15)));
+        assert_eq!(Cents(u32::MAX).checked_add(Cents(1)), None);
+        assert_eq!(Cents(u32::MAX).checked_mul(2), None);
+        assert_eq!(Cents(999).checked_percent(10), Some(Cents(99)));
+        assert_eq!(
+            Cents(u32::MAX).checked_percent(10),
+            Some(Cents(429_496_729)),
+        );
+        assert_eq!(Cents(u32::MAX).checked_percent(100), Some(Cents(u32::MAX)));
+        assert_eq!(Cents(u32::MAX).checked_percent(101), None);
+    }
+
+    #[test]
+    fn try_create_package() {
+        let package =
+            Package::try_new(String::from("Spain"), String::from("Russia"), 1200).unwrap();
+        assert!(package.is_international());
+        assert_eq!(package.get_fees(3), 3600);
+
+        let package = Package::try_new(String::from("Canada"), String::from("Canada"), 10).unwrap();
+        assert!(!package.is_international());
+    }
+
+    #[test]
+    fn try_create_package_with_country_codes() {
+        let package = Package::try_new(String::from("ES"), String::from("Spain"), 30_000).unwrap();
+        assert_eq!(package.sender_country, "Spain");
+        assert!(!package.is_international());
+
+        let package = Package::try_new(String::from("ES"), String::from("AT"), 500).unwrap();
+        assert_eq!(package.recipient_country, "Austria");
+        assert!(package.is_international());
+    }
+
+    #[test]
+    fn try_create_weightless_package() {
+        assert_eq!(
+            Package::try_new(String::from("Spain"), String::from("Austria"), 5).err(),
+            Some(PackageError::TooLight { weight_in_grams: 5 }),
+        );
+        assert_eq!(
+            Package::try_new(String::from("Spain"), String::from("Austria"), 0).err(),
+            Some(PackageError::TooLight { weight_in_grams: 0 }),
+        );
+    }
+
+    #[test]
+    fn try_create_too_heavy_package() {
+        assert_eq!(
+            Package::try_new(String::from("Spain"), String::from("Austria"), 30_001).err(),
+            Some(PackageError::TooHeavy {
+                weight_in_grams: 30_001,
+            }),
+        );
+    }
+
+    #[test]
+    fn try_create_package_with_empty_country() {
+        assert_eq!(
+            Package::try_new(String::new(), String::from("Austria"), 100).err(),
+            Some(PackageError::EmptyCountry(Party::Sender)),
+        );
+        assert_eq!(
+            Package::try_new(String::from("Spain"), String::from(" "), 100).err(),
+            Some(PackageError::EmptyCountry(Party::Recipient)),
+        );
+    }
+
+    #[test]
+    fn try_create_package_with_unknown_country() {
+        assert_eq!(
+            Package::try_new(String::from("Atlantis"), String::from("Spain"), 100).err(),
+            Some(PackageError::UnknownCountry(
+                Party::Sender,
+                String::from("Atlantis"),
+            )),
+        );
+        // Codes are case-sensitive.
+        assert_eq!(
+            Package::try_new(String::from("Spain"), String::from("es"), 100).err(),
+            Some(PackageError::UnknownCountry(
+                Party::Recipient,
+                String::from("es"),
+            )),
+        );
+    }
+
+    fn policy() -> ConsolidationPolicy {
+        ConsolidationPolicy {
+            max_box_weight_in_grams: 3_000,
+            discount_percent_per_extra_package: 5,
+            max_discount_percent: 20,
+        }
+    }
+
+    fn package(sender_country: &str, recipient_country: &str, weight_in_grams: u32) -> Package {
+        Package::new(
+            sender_country.to_string(),
+            recipient_country.to_string(),
+            weight_in_grams,
+        )
+    }
+
+    #[test]
+    fn consolidation_discount_percent() {
+        let policy = policy();
+        assert_eq!(policy.discount_percent(0), 0);
+        assert_eq!(policy.discount_percent(1), 0);
+        assert_eq!(policy.discount_percent(2), 5);
+        assert_eq!(policy.discount_percent(5), 20);
+        assert_eq!(policy.discount_percent(100), 20);
+        assert_eq!(policy.discount_percent(usize::MAX), 20);
+    }
+
+    #[test]
+    fn discount_above_100_percent() {
+        let policy = ConsolidationPolicy {
+            discount_percent_per_extra_package: 60,
+            max_discount_percent: 150,
+            ..policy()
+        };
+        assert_eq!(policy.discount_percent(2), 60);
+        assert_eq!(policy.discount_percent(3), 100);
+
+        let mut shipment = Shipment::new();
+        for _ in 0..3 {
+            shipment.add(package("Canada", "Canada", 500));
+        }
+        let invoice = shipment.invoice(&RateTable::standard(), &policy).unwrap();
+        assert_eq!(invoice.lines.len(), 1);
+        assert_eq!(invoice.lines[0].discount, invoice.lines[0].quote.total);
+        assert_eq!(invoice.lines[0].fee, Cents(0));
+        assert_eq!(invoice.total, Cents(0));
+    }
+
+    #[test]
+    fn shipment_invoice() {
+        let mut shipment = Shipment::new();
+        shipment.add(package("Spain", "Austria", 1200));
+        shipment.add(package("Spain", "Spain", 1500));
+        shipment.add(package("Canada", "Canada", 500));
+        shipment.add(package("Spain", "Austria", 1200));
+        shipment.add(package("Canada", "Canada", 500));
+        shipment.add(package("Spain", "Austria", 1200));
+
+        let invoice = shipment.invoice(&RateTable::standard(), &policy()).unwrap();
+
+        let route = |sender_country: &str, recipient_country: &str| Route {
+            sender_country: sender_country.to_string(),
+            recipient_country: recipient_country.to_string(),
+        };
+        let summary: Vec<(Route, &[usize], u32, Cents, Cents)> = invoice
+            .lines
+            .iter()
+            .map(|line| {
+                (
+                    line.route.clone(),
+                    line.packages.as_slice(),
+                    line.weight_in_grams,
+                    line.discount,
+                    line.fee,
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                // 3 cents per gram with a 5% discount.
+                (
+                    route("Canada", "Canada"),
+                    &[2, 4][..],
+                    1000,
+                    Cents(150),
+                    Cents(2_850)
+                ),
+                // The 3 packages to Austria don't fit into one box.
+                (
+                    route("Spain", "Austria"),
+                    &[0, 3][..],
+                    2400,
+                    Cents(518),
+                    Cents(9_852)
+                ),
+                (
+                    route("Spain", "Austria"),
+                    &[5][..],
+                    1200,
+                    Cents(0),
+                    Cents(6_410)
+                ),
+                (
+                    route("Spain", "Spain"),
+                    &[1][..],
+                    1500,
+                    Cents(0),
+                    Cents(4_000)
+                ),
+            ],
+        );
+        assert_eq!(invoice.lines[1].quote.surcharge, Cents(920 + 250));
+        assert_eq!(invoice.total, Cents(2_850 + 9_852 + 6_410 + 4_000));
+    }
+
+    #[test]
+    fn consolidation_is_cheaper() {
+        let mut shipment = Shipment::new();
+        for _ in 0..4 {
+            shipment.add(package("Spain", "Russia", 700));
+        }
+
+        let rate_table = RateTable::standard();
+        let invoice = shipment.invoice(&rate_table, &policy()).unwrap();
+        assert_eq!(invoice.lines.len(), 1);
+        assert_eq!(invoice.lines[0].packages, [0, 1, 2, 3]);
+
+        let separately: u32 = shipment
+            .packages
+            .iter()
+            .map(|package| rate_table.quote(package).unwrap().total.0)
+            .sum();
+        assert!(invoice.total.0 < separately);
+    }
+
+    #[test]
+    fn split_heavy_packages_into_boxes() {
+        let mut shipment = Shipment::new();
+        for weight_in_grams in [500, 2_500, 5_000, 1_000, 2_000] {
+            shipment.add(package("Canada", "Canada", weight_in_grams));
+        }
+
+        let invoice = shipment.invoice(&RateTable::standard(), &policy()).unwrap();
+        let boxes: Vec<(&[usize], u32)> = invoice
+            .lines
+            .iter()
+            .map(|line| (line.packages.as_slice(), line.weight_in_grams))
+            .collect();
+        // The package of 5 kg is heavier than the limit and ships alone.
+        assert_eq!(
+            boxes,
+            [
+                (&[2][..], 5_000),
+                (&[0, 1][..], 3_000),
+                (&[3, 4][..], 3_000)
+            ],
+        );
+        assert!(invoice
+            .lines
+            .iter()
+            .all(|line| line.packages.len() == 1 || line.weight_in_grams <= 3_000));
+    }
+
+    #[test]
+    fn empty_shipment() {
+        let invoice = Shipment::new()
+            .invoice(&RateTable::standard(), &policy())
+            .unwrap();
+        assert!(invoice.lines.is_empty());
+        assert_eq!(invoice.total, Cents(0));
+    }
+
+    #[test]
+    fn shipment_overflow() {
+        let mut shipment = Shipment::new();
+        shipment.add(package("Spain", "Spain", u32::MAX));
+        assert_eq!(
+            shipment.invoice(&RateTable::standard(), &policy()),
+            Err(RateError::Overflow),
+        );
+    }
 }
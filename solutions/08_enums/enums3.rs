@@ -1,8 +1,17 @@
+use std::error::Error;
+use std::fmt::{self, Write};
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::{self, FromStr};
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 struct Point {
     x: u64,
     y: u64,
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
 enum Message {
     Resize { width: u64, height: u64 },
     Move(Point),
@@ -11,6 +20,7 @@ enum Message {
     Quit,
 }
 
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
 struct State {
     width: u64,
     height: u64,
@@ -53,8 +63,658 @@ impl State {
     }
 }
 
-fn main() {
-    // You can optionally experiment here.
+// The values of the fields that a message is going to change. Restoring them
+// undoes the message.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum PriorValues {
+    Size { width: u64, height: u64 },
+    Position(Point),
+    Message(String),
+    Color(u8, u8, u8),
+    Quit(bool),
+}
+
+impl State {
+    fn prior_values(&self, message: &Message) -> PriorValues {
+        match message {
+            Message::Resize { .. } => PriorValues::Size {
+                width: self.width,
+                height: self.height,
+            },
+            Message::Move(_) => PriorValues::Position(self.position.clone()),
+            Message::Echo(_) => PriorValues::Message(self.message.clone()),
+            Message::ChangeColor(..) => {
+                let (red, green, blue) = self.color;
+                PriorValues::Color(red, green, blue)
+            }
+            Message::Quit => PriorValues::Quit(self.quit),
+        }
+    }
+
+    fn restore(&mut self, prior_values: PriorValues) {
+        match prior_values {
+            PriorValues::Size { width, height } => self.resize(width, height),
+            PriorValues::Position(point) => self.move_position(point),
+            PriorValues::Message(string) => self.echo(string),
+            PriorValues::Color(red, green, blue) => self.change_color(red, green, blue),
+            PriorValues::Quit(quit) => self.quit = quit,
+        }
+    }
+
+    // Rebuilds a state by processing the messages on top of `self`.
+    fn replay<I: IntoIterator<Item = Message>>(mut self, messages: I) -> Self {
+        for message in messages {
+            self.process(message);
+        }
+        self
+    }
+}
+
+// Records every processed message so that messages can be undone and redone
+// and the state after any number of messages can be rebuilt.
+#[derive(Debug, Default)]
+struct Session {
+    initial: State,
+    state: State,
+    // The processed messages with the values they changed.
+    log: Vec<(Message, PriorValues)>,
+    // Undone messages, the most recently undone one last. Processing a new
+    // message clears them.
+    redo_stack: Vec<Message>,
+    // A snapshot is taken every `snapshot_interval` messages. 0 disables
+    // snapshots.
+    snapshot_interval: usize,
+    // The length of the log when the snapshot was taken and the state.
+    snapshots: Vec<(usize, State)>,
+}
+
+impl Session {
+    fn new(initial: State, snapshot_interval: usize) -> Self {
+        Self {
+            state: initial.clone(),
+            initial,
+            snapshot_interval,
+            ..Default::default()
+        }
+    }
+
+    // Processes all messages of a log in a new session.
+    fn from_log<I: IntoIterator<Item = Message>>(
+        initial: State,
+        snapshot_interval: usize,
+        messages: I,
+    ) -> Self {
+        let mut session = Self::new(initial, snapshot_interval);
+        for message in messages {
+            session.process(message);
+        }
+        session
+    }
+
+    fn state(&self) -> &State {
+        &self.state
+    }
+
+    fn messages(&self) -> impl Iterator<Item = &Message> {
+        self.log.iter().map(|(message, _)| message)
+    }
+
+    fn process(&mut self, message: Message) {
+        self.redo_stack.clear();
+        self.record(message);
+    }
+
+    fn record(&mut self, message: Message) {
+        let prior_values = self.state.prior_values(&message);
+        self.state.process(message.clone());
+        self.log.push((message, prior_values));
+
+        if self.snapshot_interval > 0 && self.log.len().is_multiple_of(self.snapshot_interval) {
+            self.snapshots.push((self.log.len(), self.state.clone()));
+        }
+    }
+
+    // Returns the undone message or `None` if there is nothing to undo.
+    fn undo(&mut self) -> Option<&Message> {
+        let (message, prior_values) = self.log.pop()?;
+        self.state.restore(prior_values);
+
+        let len = self.log.len();
+        self.snapshots
+            .retain(|(snapshot_len, _)| *snapshot_len <= len);
+
+        self.redo_stack.push(message);
+        self.redo_stack.last()
+    }
+
+    // Returns the redone message or `None` if there is nothing to redo.
+    fn redo(&mut self) -> Option<&Message> {
+        let message = self.redo_stack.pop()?;
+        self.record(message);
+        self.log.last().map(|(message, _)| message)
+    }
+
+    // The state after the first `n_messages` messages of the log. Starts from
+    // the latest snapshot before that point instead of replaying the whole log.
+    fn state_at(&self, n_messages: usize) -> Option<State> {
+        if n_messages > self.log.len() {
+            return None;
+        }
+
+        let (start, state) = self
+            .snapshots
+            .iter()
+            .rev()
+            .find(|(snapshot_len, _)| *snapshot_len <= n_messages)
+            .map_or((0, &self.initial), |(snapshot_len, state)| {
+                (*snapshot_len, state)
+            });
+
+        let messages = self.log[start..n_messages]
+            .iter()
+            .map(|(message, _)| message.clone());
+        Some(state.clone().replay(messages))
+    }
+}
+
+// A line-based text encoding of messages, e.g. `RESIZE 10 30`, `MOVE 10 15`,
+// `ECHO "hello world"`, `COLOR 200 255 255` and `QUIT`. The parser is strict:
+// it only accepts exactly what `Display` writes. In `ECHO` strings, `"`, `\`,
+// line feeds, carriage returns and tabs are escaped with a backslash.
+#[derive(Debug, PartialEq, Eq)]
+enum ParseMessageError {
+    UnknownCommand(String),
+    MissingArgument,
+    UnexpectedArgument,
+    // Not a number in decimal notation without sign and leading zeros, or out
+    // of range.
+    InvalidNumber(String),
+    ExpectedQuote,
+    UnterminatedString,
+    InvalidEscape(char),
+    // Line breaks in strings have to be escaped.
+    UnescapedLineBreak,
+}
+
+impl fmt::Display for ParseMessageError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseMessageError::UnknownCommand(command) => write!(f, "unknown command `{command}`"),
+            ParseMessageError::MissingArgument => f.write_str("missing argument"),
+            ParseMessageError::UnexpectedArgument => f.write_str("unexpected argument"),
+            ParseMessageError::InvalidNumber(number) => write!(f, "invalid number `{number}`"),
+            ParseMessageError::ExpectedQuote => f.write_str("expected a string in quotes"),
+            ParseMessageError::UnterminatedString => f.write_str("missing closing quote"),
+            ParseMessageError::InvalidEscape(c) => write!(f, "invalid escape `\\{c}`"),
+            ParseMessageError::UnescapedLineBreak => f.write_str("unescaped line break"),
+        }
+    }
+}
+
+impl Error for ParseMessageError {}
+
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Message::Resize { width, height } => write!(f, "RESIZE {width} {height}"),
+            Message::Move(Point { x, y }) => write!(f, "MOVE {x} {y}"),
+            Message::Echo(string) => {
+                f.write_str("ECHO \"")?;
+                for c in string.chars() {
+                    match c {
+                        '"' => f.write_str("\\\"")?,
+                        '\\' => f.write_str("\\\\")?,
+                        '\n' => f.write_str("\\n")?,
+                        '\r' => f.write_str("\\r")?,
+                        '\t' => f.write_str("\\t")?,
+                        c => f.write_char(c)?,
+                    }
+                }
+                f.write_char('"')
+            }
+            Message::ChangeColor(red, green, blue) => write!(f, "COLOR {red} {green} {blue}"),
+            Message::Quit => f.write_str("QUIT"),
+        }
+    }
+}
+
+// Parses a number without sign and leading zeros.
+fn parse_number<T: FromStr>(s: &str) -> Result<T, ParseMessageError> {
+    let invalid = || ParseMessageError::InvalidNumber(s.to_string());
+
+    if s.is_empty() || !s.bytes().all(|b| b.is_ascii_digit()) || (s.len() > 1 && s.starts_with('0'))
+    {
+        return Err(invalid());
+    }
+
+    s.parse().map_err(|_| invalid())
+}
+
+// Parses exactly `N` numbers separated by single spaces.
+fn parse_numbers<T: FromStr + Copy + Default, const N: usize>(
+    arguments: Option<&str>,
+) -> Result<[T; N], ParseMessageError> {
+    let mut numbers = [T::default(); N];
+    let mut arguments = arguments
+        .ok_or(ParseMessageError::MissingArgument)?
+        .split(' ');
+
+    for number in &mut numbers {
+        let argument = arguments.next().ok_or(ParseMessageError::MissingArgument)?;
+        *number = parse_number(argument)?;
+    }
+
+    if arguments.next().is_some() {
+        return Err(ParseMessageError::UnexpectedArgument);
+    }
+
+    Ok(numbers)
+}
+
+fn parse_quoted_string(s: &str) -> Result<String, ParseMessageError> {
+    let mut chars = s
+        .strip_prefix('"')
+        .ok_or(ParseMessageError::ExpectedQuote)?
+        .chars();
+    let mut string = String::new();
+
+    loop {
+        match chars.next().ok_or(ParseMessageError::UnterminatedString)? {
+            '"' => break,
+            '\\' => {
+                let escaped = match chars.next().ok_or(ParseMessageError::UnterminatedString)? {
+                    c @ ('"' | '\\') => c,
+                    'n' => '\n',
+                    'r' => '\r',
+                    't' => '\t',
+                    c => return Err(ParseMessageError::InvalidEscape(c)),
+                };
+                string.push(escaped);
+            }
+            // Unescaped line breaks would split the message into two lines.
+            '\n' | '\r' => return Err(ParseMessageError::UnescapedLineBreak),
+            c => string.push(c),
+        }
+    }
+
+    if chars.next().is_some() {
+        return Err(ParseMessageError::UnexpectedArgument);
+    }
+
+    Ok(string)
+}
+
+impl FromStr for Message {
+    type Err = ParseMessageError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (command, arguments) = match s.split_once(' ') {
+            Some((command, arguments)) => (command, Some(arguments)),
+            None => (s, None),
+        };
+
+        match command {
+            "RESIZE" => {
+                let [width, height] = parse_numbers(arguments)?;
+                Ok(Message::Resize { width, height })
+            }
+            "MOVE" => {
+                let [x, y] = parse_numbers(arguments)?;
+                Ok(Message::Move(Point { x, y }))
+            }
+            "ECHO" => {
+                let arguments = arguments.ok_or(ParseMessageError::MissingArgument)?;
+                parse_quoted_string(arguments).map(Message::Echo)
+            }
+            "COLOR" => {
+                let [red, green, blue] = parse_numbers(arguments)?;
+                Ok(Message::ChangeColor(red, green, blue))
+            }
+            "QUIT" => match arguments {
+                Some(_) => Err(ParseMessageError::UnexpectedArgument),
+                None => Ok(Message::Quit),
+            },
+            _ => Err(ParseMessageError::UnknownCommand(command.to_string())),
+        }
+    }
+}
+
+// A compact binary encoding of messages and states. Every encoded message or
+// state starts with the version of the encoding. Numbers are little-endian,
+// strings are prefixed with their length in bytes as a `u32`, so longer strings
+// can't be encoded.
+const CODEC_VERSION: u8 = 1;
+
+const TAG_RESIZE: u8 = 0;
+const TAG_MOVE: u8 = 1;
+const TAG_ECHO: u8 = 2;
+const TAG_CHANGE_COLOR: u8 = 3;
+const TAG_QUIT: u8 = 4;
+
+#[derive(Debug, PartialEq, Eq)]
+enum DecodeError {
+    UnsupportedVersion(u8),
+    // The input ended in the middle of a value.
+    Truncated,
+    UnknownTag(u8),
+    InvalidUtf8,
+    InvalidBool(u8),
+    // The number of bytes left after the decoded value.
+    TrailingBytes(usize),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DecodeError::UnsupportedVersion(version) => {
+                write!(f, "unsupported encoding version {version}")
+            }
+            DecodeError::Truncated => f.write_str("unexpected end of input"),
+            DecodeError::UnknownTag(tag) => write!(f, "unknown message tag {tag}"),
+            DecodeError::InvalidUtf8 => f.write_str("string isn't valid UTF-8"),
+            DecodeError::InvalidBool(byte) => write!(f, "invalid boolean {byte}"),
+            DecodeError::TrailingBytes(n) => write!(f, "{n} bytes left after the end"),
+        }
+    }
+}
+
+impl Error for DecodeError {}
+
+#[derive(Debug, PartialEq, Eq)]
+enum EncodeError {
+    // The length in bytes of a string that is longer than `u32::MAX` bytes.
+    TooLong(usize),
+}
+
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EncodeError::TooLong(len) => write!(f, "string of {len} bytes is too long to encode"),
+        }
+    }
+}
+
+impl Error for EncodeError {}
+
+struct Encoder {
+    bytes: Vec<u8>,
+}
+
+impl Encoder {
+    fn new() -> Self {
+        Self {
+            bytes: vec![CODEC_VERSION],
+        }
+    }
+
+    fn u8(&mut self, value: u8) {
+        self.bytes.push(value);
+    }
+
+    fn u64(&mut self, value: u64) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    fn bool(&mut self, value: bool) {
+        self.u8(u8::from(value));
+    }
+
+    // The length prefix of a string.
+    fn len(&mut self, len: usize) -> Result<(), EncodeError> {
+        let len = u32::try_from(len).map_err(|_| EncodeError::TooLong(len))?;
+        self.bytes.extend_from_slice(&len.to_le_bytes());
+        Ok(())
+    }
+
+    fn string(&mut self, value: &str) -> Result<(), EncodeError> {
+        self.len(value.len())?;
+        self.bytes.extend_from_slice(value.as_bytes());
+        Ok(())
+    }
+
+    fn message(&mut self, message: &Message) -> Result<(), EncodeError> {
+        match message {
+            Message::Resize { width, height } => {
+                self.u8(TAG_RESIZE);
+                self.u64(*width);
+                self.u64(*height);
+            }
+            Message::Move(Point { x, y }) => {
+                self.u8(TAG_MOVE);
+                self.u64(*x);
+                self.u64(*y);
+            }
+            Message::Echo(string) => {
+                self.u8(TAG_ECHO);
+                self.string(string)?;
+            }
+            Message::ChangeColor(red, green, blue) => {
+                self.u8(TAG_CHANGE_COLOR);
+                self.u8(*red);
+                self.u8(*green);
+                self.u8(*blue);
+            }
+            Message::Quit => self.u8(TAG_QUIT),
+        }
+        Ok(())
+    }
+}
+
+struct Decoder<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Decoder<'a> {
+    // Checks the version at the start of the input.
+    fn new(bytes: &'a [u8]) -> Result<Self, DecodeError> {
+        let mut decoder = Self { bytes };
+        match decoder.u8()? {
+            CODEC_VERSION => Ok(decoder),
+            version => Err(DecodeError::UnsupportedVersion(version)),
+        }
+    }
+
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], DecodeError> {
+        let (bytes, rest) = self
+            .bytes
+            .split_first_chunk()
+            .ok_or(DecodeError::Truncated)?;
+        self.bytes = rest;
+        Ok(*bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, DecodeError> {
+        self.take::<1>().map(|[byte]| byte)
+    }
+
+    fn u64(&mut self) -> Result<u64, DecodeError> {
+        self.take().map(u64::from_le_bytes)
+    }
+
+    fn bool(&mut self) -> Result<bool, DecodeError> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            byte => Err(DecodeError::InvalidBool(byte)),
+        }
+    }
+
+    fn string(&mut self) -> Result<String, DecodeError> {
+        let len = u32::from_le_bytes(self.take()?) as usize;
+        if self.bytes.len() < len {
+            return Err(DecodeError::Truncated);
+        }
+
+        let (bytes, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        String::from_utf8(bytes.to_vec()).map_err(|_| DecodeError::InvalidUtf8)
+    }
+
+    fn message(&mut self) -> Result<Message, DecodeError> {
+        let message = match self.u8()? {
+            TAG_RESIZE => Message::Resize {
+                width: self.u64()?,
+                height: self.u64()?,
+            },
+            TAG_MOVE => Message::Move(Point {
+                x: self.u64()?,
+                y: self.u64()?,
+            }),
+            TAG_ECHO => Message::Echo(self.string()?),
+            TAG_CHANGE_COLOR => Message::ChangeColor(self.u8()?, self.u8()?, self.u8()?),
+            TAG_QUIT => Message::Quit,
+            tag => return Err(DecodeError::UnknownTag(tag)),
+        };
+        Ok(message)
+    }
+
+    fn finish(self) -> Result<(), DecodeError> {
+        match self.bytes.len() {
+            0 => Ok(()),
+            n => Err(DecodeError::TrailingBytes(n)),
+        }
+    }
+}
+
+impl Message {
+    fn to_bytes(&self) -> Result<Vec<u8>, EncodeError> {
+        let mut encoder = Encoder::new();
+        encoder.message(self)?;
+        Ok(encoder.bytes)
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let mut decoder = Decoder::new(bytes)?;
+        let message = decoder.message()?;
+        decoder.finish()?;
+        Ok(message)
+    }
+}
+
+#[derive(Debug)]
+enum LoadStateError {
+    Io(io::Error),
+    Decode(DecodeError),
+}
+
+impl fmt::Display for LoadStateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LoadStateError::Io(e) => e.fmt(f),
+            LoadStateError::Decode(e) => e.fmt(f),
+        }
+    }
+}
+
+impl Error for LoadStateError {}
+
+impl State {
+    fn to_bytes(&self) -> Result<Vec<u8>, EncodeError> {
+        let mut encoder = Encoder::new();
+        encoder.u64(self.width);
+        encoder.u64(self.height);
+        encoder.u64(self.position.x);
+        encoder.u64(self.position.y);
+        encoder.string(&self.message)?;
+        let (red, green, blue) = self.color;
+        encoder.u8(red);
+        encoder.u8(green);
+        encoder.u8(blue);
+        encoder.bool(self.quit);
+        Ok(encoder.bytes)
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let mut decoder = Decoder::new(bytes)?;
+        let state = Self {
+            width: decoder.u64()?,
+            height: decoder.u64()?,
+            position: Point {
+                x: decoder.u64()?,
+                y: decoder.u64()?,
+            },
+            message: decoder.string()?,
+            color: (decoder.u8()?, decoder.u8()?, decoder.u8()?),
+            quit: decoder.bool()?,
+        };
+        decoder.finish()?;
+        Ok(state)
+    }
+
+    fn save(&self, path: &Path) -> io::Result<()> {
+        let bytes = self
+            .to_bytes()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        fs::write(path, bytes)
+    }
+
+    fn load(path: &Path) -> Result<Self, LoadStateError> {
+        let bytes = fs::read(path).map_err(LoadStateError::Io)?;
+        Self::from_bytes(&bytes).map_err(LoadStateError::Decode)
+    }
+}
+
+impl fmt::Display for State {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (red, green, blue) = self.color;
+        write!(
+            f,
+            "size={}x{} position=({}, {}) color=({red}, {green}, {blue}) message={:?} quit={}",
+            self.width, self.height, self.position.x, self.position.y, self.message, self.quit,
+        )
+    }
+}
+
+// Reads messages in the text encoding line by line and prints the state after
+// each one. Invalid lines are reported to `errors` and skipped. Stops after
+// `QUIT` or at the end of the input and returns the final state.
+fn run_shell<R, W, E>(mut input: R, mut output: W, mut errors: E) -> io::Result<State>
+where
+    R: io::BufRead,
+    W: io::Write,
+    E: io::Write,
+{
+    let mut state = State::default();
+    let mut buf = Vec::new();
+
+    // Reads bytes instead of `String`s to report lines with invalid UTF-8
+    // like other bad input instead of stopping.
+    for line_number in 1.. {
+        buf.clear();
+        if input.read_until(b'\n', &mut buf)? == 0 {
+            break;
+        }
+        let line = match str::from_utf8(&buf) {
+            Ok(line) => line,
+            Err(e) => {
+                writeln!(errors, "line {line_number}: {e}")?;
+                continue;
+            }
+        };
+        let line = line.strip_suffix('\n').unwrap_or(line);
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        if line.is_empty() {
+            continue;
+        }
+
+        match line.parse::<Message>() {
+            Ok(message) => {
+                state.process(message);
+                writeln!(output, "{state}")?;
+                if state.quit {
+                    break;
+                }
+            }
+            Err(e) => writeln!(errors, "line {line_number}: {e}")?,
+        }
+    }
+
+    Ok(state)
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    run_shell(io::stdin().lock(), io::stdout().lock(), io::stderr().lock())?;
+    Ok(())
 }
 
 #[cfg(test)]
@@ -89,4 +749,510 @@ mod tests {
         assert_eq!(state.color, (255, 0, 255));
         assert!(state.quit);
     }
+
+    fn messages() -> Vec<Message> {
+        vec![
+            Message::Resize {
+                width: 10,
+                height: 30,
+            },
+            Message::Move(Point { x: 10, y: 15 }),
+            Message::Echo(String::from("Hello world!")),
+            Message::ChangeColor(255, 0, 255),
+            Message::Resize {
+                width: 20,
+                height: 40,
+            },
+            Message::Echo(String::from("Bye!")),
+            Message::Quit,
+        ]
+    }
+
+    #[test]
+    fn undo_restores_prior_values() {
+        let mut session = Session::from_log(State::default(), 0, messages());
+        assert!(session.state().quit);
+        assert_eq!(session.messages().count(), 7);
+
+        assert_eq!(session.undo(), Some(&Message::Quit));
+        assert!(!session.state().quit);
+        assert_eq!(session.undo(), Some(&Message::Echo(String::from("Bye!"))));
+        assert_eq!(session.state().message, "Hello world!");
+        session.undo();
+        assert_eq!((session.state().width, session.state().height), (10, 30));
+
+        while session.undo().is_some() {}
+        assert_eq!(*session.state(), State::default());
+        assert_eq!(session.messages().count(), 0);
+    }
+
+    #[test]
+    fn redo() {
+        let mut session = Session::from_log(State::default(), 0, messages());
+        let final_state = session.state().clone();
+
+        for _ in 0..3 {
+            session.undo();
+        }
+        assert_eq!(session.redo(), Some(&messages()[4]));
+        assert_eq!(session.state().width, 20);
+        session.redo();
+        session.redo();
+        assert_eq!(session.redo(), None);
+        assert_eq!(*session.state(), final_state);
+
+        // A new message clears the undone messages.
+        session.undo();
+        session.process(Message::Echo(String::from("Again")));
+        assert_eq!(session.redo(), None);
+        assert!(!session.state().quit);
+        assert_eq!(session.state().message, "Again");
+    }
+
+    #[test]
+    fn undo_quit_twice() {
+        let mut session = Session::from_log(State::default(), 0, [Message::Quit, Message::Quit]);
+        session.undo();
+        assert!(session.state().quit);
+        session.undo();
+        assert!(!session.state().quit);
+    }
+
+    #[test]
+    fn replay_message_log() {
+        let session = Session::from_log(State::default(), 0, messages());
+        let messages: Vec<Message> = session.messages().cloned().collect();
+        assert_eq!(State::default().replay(messages), *session.state());
+    }
+
+    #[test]
+    fn state_at_with_snapshots() {
+        for snapshot_interval in [0, 1, 2, 3, 10] {
+            let session = Session::from_log(State::default(), snapshot_interval, messages());
+
+            let mut state = State::default();
+            assert_eq!(session.state_at(0), Some(state.clone()));
+            for (ind, message) in messages().into_iter().enumerate() {
+                state.process(message);
+                assert_eq!(session.state_at(ind + 1), Some(state.clone()));
+            }
+            assert_eq!(session.state_at(8), None);
+        }
+    }
+
+    #[test]
+    fn snapshots() {
+        let mut session = Session::from_log(State::default(), 3, messages());
+        let lens: Vec<usize> = session.snapshots.iter().map(|(len, _)| *len).collect();
+        assert_eq!(lens, [3, 6]);
+        assert_eq!(session.snapshots[1].1.message, "Bye!");
+
+        // Undoing drops the snapshots that are newer than the log.
+        session.undo();
+        session.undo();
+        assert_eq!(session.snapshots.len(), 1);
+        assert_eq!(session.state_at(5).unwrap(), *session.state());
+
+        session.redo();
+        assert_eq!(session.snapshots.len(), 2);
+    }
+
+    fn parse(line: &str) -> Result<Message, ParseMessageError> {
+        line.parse()
+    }
+
+    #[test]
+    fn encode_messages() {
+        let encoded: Vec<String> = messages().iter().map(Message::to_string).collect();
+        assert_eq!(
+            encoded,
+            [
+                "RESIZE 10 30",
+                "MOVE 10 15",
+                "ECHO \"Hello world!\"",
+                "COLOR 255 0 255",
+                "RESIZE 20 40",
+                "ECHO \"Bye!\"",
+                "QUIT",
+            ],
+        );
+        assert_eq!(
+            Message::Echo(String::from("say \"hi\"\\\n\tbye\r")).to_string(),
+            r#"ECHO "say \"hi\"\\\n\tbye\r""#,
+        );
+    }
+
+    #[test]
+    fn decode_messages() {
+        assert_eq!(
+            parse("RESIZE 10 30"),
+            Ok(Message::Resize {
+                width: 10,
+                height: 30,
+            }),
+        );
+        assert_eq!(
+            parse("MOVE 0 18446744073709551615"),
+            Ok(Message::Move(Point { x: 0, y: u64::MAX }))
+        );
+        assert_eq!(
+            parse("ECHO \"hello world\""),
+            Ok(Message::Echo(String::from("hello world")))
+        );
+        assert_eq!(parse("ECHO \"\""), Ok(Message::Echo(String::new())));
+        assert_eq!(
+            parse(r#"ECHO "a\"b\\c\nd""#),
+            Ok(Message::Echo(String::from("a\"b\\c\nd")))
+        );
+        assert_eq!(
+            parse("COLOR 200 255 255"),
+            Ok(Message::ChangeColor(200, 255, 255))
+        );
+        assert_eq!(parse("QUIT"), Ok(Message::Quit));
+    }
+
+    #[test]
+    fn decode_errors() {
+        use ParseMessageError::*;
+
+        let invalid_number = |number: &str| Err(InvalidNumber(number.to_string()));
+
+        assert_eq!(parse(""), Err(UnknownCommand(String::new())));
+        assert_eq!(parse("quit"), Err(UnknownCommand(String::from("quit"))));
+        assert_eq!(parse(" QUIT"), Err(UnknownCommand(String::new())));
+        assert_eq!(parse("QUIT "), Err(UnexpectedArgument));
+        assert_eq!(parse("QUIT now"), Err(UnexpectedArgument));
+        assert_eq!(parse("RESIZE"), Err(MissingArgument));
+        assert_eq!(parse("RESIZE 10"), Err(MissingArgument));
+        assert_eq!(parse("RESIZE 10 30 1"), Err(UnexpectedArgument));
+        assert_eq!(parse("RESIZE 10  30"), invalid_number(""));
+        assert_eq!(parse("RESIZE 10 30 "), Err(UnexpectedArgument));
+        assert_eq!(parse("MOVE -1 0"), invalid_number("-1"));
+        assert_eq!(parse("MOVE +1 0"), invalid_number("+1"));
+        assert_eq!(parse("MOVE 01 0"), invalid_number("01"));
+        assert_eq!(
+            parse("MOVE 1 18446744073709551616"),
+            invalid_number("18446744073709551616")
+        );
+        assert_eq!(parse("COLOR 256 0 0"), invalid_number("256"));
+        assert_eq!(parse("COLOR 1 2 x"), invalid_number("x"));
+        assert_eq!(parse("ECHO"), Err(MissingArgument));
+        assert_eq!(parse("ECHO hello"), Err(ExpectedQuote));
+        assert_eq!(parse("ECHO \"hello"), Err(UnterminatedString));
+        assert_eq!(parse("ECHO \"hello\\\""), Err(UnterminatedString));
+        assert_eq!(parse("ECHO \"a\" b"), Err(UnexpectedArgument));
+        assert_eq!(parse("ECHO \"a\\x\""), Err(InvalidEscape('x')));
+        assert_eq!(parse("ECHO \"a\nb\""), Err(UnescapedLineBreak));
+    }
+
+    #[test]
+    fn error_display() {
+        assert_eq!(
+            parse("JUMP 1").unwrap_err().to_string(),
+            "unknown command `JUMP`"
+        );
+        assert_eq!(
+            parse("ECHO \"\\q\"").unwrap_err().to_string(),
+            "invalid escape `\\q`"
+        );
+    }
+
+    // A small pseudo-random number generator so that the tests don't need
+    // any dependencies.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 = self
+                .0
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            self.0 >> 11
+        }
+
+        // Mostly small numbers with a few edge cases.
+        fn number(&mut self) -> u64 {
+            match self.next() % 4 {
+                0 => 0,
+                1 => u64::MAX - self.next() % 2,
+                _ => self.next() % 1000,
+            }
+        }
+
+        fn string(&mut self) -> String {
+            const CHARS: [char; 12] = [
+                'a', 'Z', ' ', '"', '\\', '\n', '\r', '\t', 'é', '日', '0', '|',
+            ];
+            let len = self.next() % 12;
+            (0..len)
+                .map(|_| CHARS[(self.next() % CHARS.len() as u64) as usize])
+                .collect()
+        }
+
+        fn message(&mut self) -> Message {
+            match self.next() % 5 {
+                0 => Message::Resize {
+                    width: self.number(),
+                    height: self.number(),
+                },
+                1 => Message::Move(Point {
+                    x: self.number(),
+                    y: self.number(),
+                }),
+                2 => Message::Echo(self.string()),
+                3 => Message::ChangeColor(self.next() as u8, self.next() as u8, self.next() as u8),
+                _ => Message::Quit,
+            }
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut rng = Rng(42);
+        let mut seen_variants = [false; 5];
+
+        for _ in 0..10_000 {
+            let message = rng.message();
+            seen_variants[match message {
+                Message::Resize { .. } => 0,
+                Message::Move(_) => 1,
+                Message::Echo(_) => 2,
+                Message::ChangeColor(..) => 3,
+                Message::Quit => 4,
+            }] = true;
+
+            let encoded = message.to_string();
+            assert!(!encoded.contains(['\n', '\r']), "{encoded}");
+            assert_eq!(parse(&encoded), Ok(message));
+        }
+
+        assert!(seen_variants.iter().all(|&seen| seen));
+    }
+
+    #[test]
+    fn round_trip_message_log() {
+        let log: String = messages()
+            .iter()
+            .map(|message| format!("{message}\n"))
+            .collect();
+        let decoded: Vec<Message> = log.lines().map(|line| parse(line).unwrap()).collect();
+        assert_eq!(decoded, messages());
+    }
+
+    #[test]
+    fn encode_message_bytes() {
+        assert_eq!(Message::Quit.to_bytes().unwrap(), [CODEC_VERSION, TAG_QUIT]);
+        assert_eq!(
+            Message::ChangeColor(1, 2, 3).to_bytes().unwrap(),
+            [CODEC_VERSION, TAG_CHANGE_COLOR, 1, 2, 3],
+        );
+        assert_eq!(
+            Message::Echo(String::from("hé")).to_bytes().unwrap(),
+            [CODEC_VERSION, TAG_ECHO, 3, 0, 0, 0, b'h', 0xc3, 0xa9],
+        );
+        assert_eq!(
+            Message::Move(Point { x: 1, y: 256 }).to_bytes().unwrap(),
+            [
+                CODEC_VERSION,
+                TAG_MOVE,
+                1,
+                0,
+                0,
+                0,
+                0,
+                0,
+                0,
+                0,
+                0,
+                1,
+                0,
+                0,
+                0,
+                0,
+                0,
+                0,
+            ],
+        );
+    }
+
+    #[test]
+    fn encode_too_long() {
+        let mut encoder = Encoder::new();
+        assert_eq!(encoder.len(u32::MAX as usize), Ok(()));
+        let too_long = u32::MAX as usize + 1;
+        assert_eq!(encoder.len(too_long), Err(EncodeError::TooLong(too_long)));
+        assert_eq!(
+            EncodeError::TooLong(too_long).to_string(),
+            "string of 4294967296 bytes is too long to encode",
+        );
+    }
+
+    #[test]
+    fn binary_round_trip() {
+        let mut rng = Rng(7);
+        for _ in 0..10_000 {
+            let message = rng.message();
+            assert_eq!(
+                Message::from_bytes(&message.to_bytes().unwrap()),
+                Ok(message)
+            );
+        }
+
+        let state = State::default().replay(messages());
+        assert_eq!(State::from_bytes(&state.to_bytes().unwrap()), Ok(state));
+        assert_eq!(
+            State::from_bytes(&State::default().to_bytes().unwrap()),
+            Ok(State::default()),
+        );
+    }
+
+    #[test]
+    fn decode_truncated() {
+        for message in messages() {
+            let bytes = message.to_bytes().unwrap();
+            // Every prefix is missing at least one byte, even the empty one
+            // which is missing the version.
+            for len in 0..bytes.len() {
+                assert_eq!(
+                    Message::from_bytes(&bytes[..len]),
+                    Err(DecodeError::Truncated)
+                );
+            }
+        }
+
+        let bytes = State::default().replay(messages()).to_bytes().unwrap();
+        for len in 0..bytes.len() {
+            assert_eq!(
+                State::from_bytes(&bytes[..len]),
+                Err(DecodeError::Truncated)
+            );
+        }
+    }
+
+    #[test]
+    fn decode_errors_binary() {
+        assert_eq!(
+            Message::from_bytes(&[CODEC_VERSION + 1, TAG_QUIT]),
+            Err(DecodeError::UnsupportedVersion(CODEC_VERSION + 1)),
+        );
+        assert_eq!(
+            Message::from_bytes(&[CODEC_VERSION, 5]),
+            Err(DecodeError::UnknownTag(5)),
+        );
+        assert_eq!(
+            Message::from_bytes(&[CODEC_VERSION, TAG_QUIT, 0, 0]),
+            Err(DecodeError::TrailingBytes(2)),
+        );
+        assert_eq!(
+            Message::from_bytes(&[CODEC_VERSION, TAG_ECHO, 1, 0, 0, 0, 0xff]),
+            Err(DecodeError::InvalidUtf8),
+        );
+        // A length that is much larger than the input.
+        assert_eq!(
+            Message::from_bytes(&[CODEC_VERSION, TAG_ECHO, 0xff, 0xff, 0xff, 0xff, b'a']),
+            Err(DecodeError::Truncated),
+        );
+
+        let mut bytes = State::default().to_bytes().unwrap();
+        *bytes.last_mut().unwrap() = 2;
+        assert_eq!(State::from_bytes(&bytes), Err(DecodeError::InvalidBool(2)));
+    }
+
+    #[test]
+    fn save_and_load_state() {
+        let path = std::env::temp_dir().join(format!("enums3_state_{}.bin", std::process::id()));
+        let state = State::default().replay(messages());
+
+        state.save(&path).unwrap();
+        let loaded = State::load(&path);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(loaded.unwrap(), state);
+
+        assert!(matches!(State::load(&path), Err(LoadStateError::Io(_))));
+
+        fs::write(&path, [CODEC_VERSION]).unwrap();
+        let loaded = State::load(&path);
+        fs::remove_file(&path).unwrap();
+        assert!(matches!(
+            loaded,
+            Err(LoadStateError::Decode(DecodeError::Truncated))
+        ));
+    }
+
+    // Runs the shell on the input and returns the final state, the output and
+    // the errors.
+    fn shell(input: &str) -> (State, String, String) {
+        let mut output = Vec::new();
+        let mut errors = Vec::new();
+        let state = run_shell(input.as_bytes(), &mut output, &mut errors).unwrap();
+        (
+            state,
+            String::from_utf8(output).unwrap(),
+            String::from_utf8(errors).unwrap(),
+        )
+    }
+
+    #[test]
+    fn shell_prints_state_after_each_message() {
+        let (state, output, errors) =
+            shell("RESIZE 10 30\nMOVE 10 15\nECHO \"hi\"\nCOLOR 255 0 255\n");
+        assert_eq!(
+            output,
+            r#"size=10x30 position=(0, 0) color=(0, 0, 0) message="" quit=false
+size=10x30 position=(10, 15) color=(0, 0, 0) message="" quit=false
+size=10x30 position=(10, 15) color=(0, 0, 0) message="hi" quit=false
+size=10x30 position=(10, 15) color=(255, 0, 255) message="hi" quit=false
+"#,
+        );
+        assert!(errors.is_empty());
+        assert!(!state.quit);
+        assert_eq!(state.color, (255, 0, 255));
+    }
+
+    #[test]
+    fn shell_stops_at_quit() {
+        let (state, output, _) = shell("MOVE 1 2\nQUIT\nMOVE 3 4\n");
+        assert!(state.quit);
+        assert_eq!((state.position.x, state.position.y), (1, 2));
+        assert_eq!(output.lines().count(), 2);
+        assert!(output.ends_with("quit=true\n"));
+    }
+
+    #[test]
+    fn shell_reports_bad_input_and_continues() {
+        let (state, output, errors) = shell("MOVE 1\n\nJUMP\r\nRESIZE 1 2\r\nECHO \"oops\nQUIT");
+        assert_eq!(
+            errors,
+            "line 1: missing argument
+line 3: unknown command `JUMP`
+line 5: missing closing quote
+",
+        );
+        assert_eq!(output.lines().count(), 2);
+        assert_eq!((state.width, state.height), (1, 2));
+        assert!(state.quit);
+    }
+
+    #[test]
+    fn shell_reports_invalid_utf8_and_continues() {
+        let mut output = Vec::new();
+        let mut errors = Vec::new();
+        let input = b"MOVE 1 2\nECHO \"\xff\xfe\"\nRESIZE 3 4\n";
+        let state = run_shell(&input[..], &mut output, &mut errors).unwrap();
+        assert_eq!(
+            String::from_utf8(errors).unwrap(),
+            "line 2: invalid utf-8 sequence of 1 bytes from index 6\n",
+        );
+        assert_eq!(String::from_utf8(output).unwrap().lines().count(), 2);
+        assert_eq!((state.position.x, state.position.y), (1, 2));
+        assert_eq!((state.width, state.height), (3, 4));
+    }
+
+    #[test]
+    fn shell_with_buf_reader() {
+        let input = io::BufReader::with_capacity(4, "ECHO \"a long message\"\nQUIT".as_bytes());
+        let state = run_shell(input, io::sink(), io::sink()).unwrap();
+        assert_eq!(state.message, "a long message");
+        assert!(state.quit);
+    }
 }
@@ -7,9 +7,12 @@
 // conceded.
 
 use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::num::ParseIntError;
 
 // A structure to store the goal details of a team.
-#[derive(Default)]
+#[derive(Default, Debug)]
 struct TeamScores {
     goals_scored: u8,
     goals_conceded: u8,
@@ -42,6 +45,462 @@ fn build_scores_table(results: &str) -> HashMap<&str, TeamScores> {
     scores
 }
 
+// The number of points awarded for each match outcome. Most leagues use 3
+// points for a win, 1 for a draw and 0 for a loss.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct PointsSystem {
+    win: u32,
+    draw: u32,
+    loss: u32,
+}
+
+impl Default for PointsSystem {
+    fn default() -> Self {
+        Self {
+            win: 3,
+            draw: 1,
+            loss: 0,
+        }
+    }
+}
+
+// One line of the results, e.g. "England,France,4,2".
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct MatchResult<'a> {
+    team_1_name: &'a str,
+    team_2_name: &'a str,
+    team_1_score: u8,
+    team_2_score: u8,
+}
+
+impl<'a> MatchResult<'a> {
+    fn parse(line: &'a str) -> Self {
+        let mut split_iterator = line.split(',');
+        // NOTE: Same `unwrap`s as in `build_scores_table`.
+        Self {
+            team_1_name: split_iterator.next().unwrap(),
+            team_2_name: split_iterator.next().unwrap(),
+            team_1_score: split_iterator.next().unwrap().parse().unwrap(),
+            team_2_score: split_iterator.next().unwrap().parse().unwrap(),
+        }
+    }
+
+    // Like `parse`, but returns an error instead of panicking. Whitespace
+    // around the fields is ignored.
+    fn try_parse(line: &'a str) -> Result<Self, ParseLineErrorKind> {
+        let mut split_iterator = line.split(',').map(str::trim);
+        let mut next_field = |field| {
+            split_iterator
+                .next()
+                .ok_or(ParseLineErrorKind::MissingField(field))
+        };
+
+        let team_1_name = next_field(Field::Team1Name)?;
+        let team_2_name = next_field(Field::Team2Name)?;
+        let team_1_score = next_field(Field::Team1Score)?;
+        let team_2_score = next_field(Field::Team2Score)?;
+
+        if split_iterator.next().is_some() {
+            return Err(ParseLineErrorKind::ExtraField);
+        }
+
+        if team_1_name.is_empty() {
+            return Err(ParseLineErrorKind::EmptyTeamName(Field::Team1Name));
+        }
+        if team_2_name.is_empty() {
+            return Err(ParseLineErrorKind::EmptyTeamName(Field::Team2Name));
+        }
+        if team_1_name == team_2_name {
+            return Err(ParseLineErrorKind::SameTeam);
+        }
+
+        let parse_score = |score: &str, field| {
+            score
+                .parse()
+                .map_err(|e| ParseLineErrorKind::BadScore(field, e))
+        };
+
+        Ok(Self {
+            team_1_name,
+            team_2_name,
+            team_1_score: parse_score(team_1_score, Field::Team1Score)?,
+            team_2_score: parse_score(team_2_score, Field::Team2Score)?,
+        })
+    }
+
+    // Whether both teams of the match are in `teams`.
+    fn is_between(&self, teams: &[&str]) -> bool {
+        teams.contains(&self.team_1_name) && teams.contains(&self.team_2_name)
+    }
+}
+
+// The full record of a team in a league.
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
+struct Standing {
+    played: u32,
+    wins: u32,
+    draws: u32,
+    losses: u32,
+    goals_scored: u32,
+    goals_conceded: u32,
+    points: u32,
+}
+
+impl Standing {
+    fn goal_difference(&self) -> i64 {
+        i64::from(self.goals_scored) - i64::from(self.goals_conceded)
+    }
+
+    fn record(&mut self, scored: u8, conceded: u8, points_system: PointsSystem) {
+        self.played += 1;
+        self.goals_scored += u32::from(scored);
+        self.goals_conceded += u32::from(conceded);
+
+        if scored > conceded {
+            self.wins += 1;
+            self.points += points_system.win;
+        } else if scored == conceded {
+            self.draws += 1;
+            self.points += points_system.draw;
+        } else {
+            self.losses += 1;
+            self.points += points_system.loss;
+        }
+    }
+}
+
+// The fields of a line of the results.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Field {
+    Team1Name,
+    Team2Name,
+    Team1Score,
+    Team2Score,
+}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let description = match *self {
+            Field::Team1Name => "name of team 1",
+            Field::Team2Name => "name of team 2",
+            Field::Team1Score => "goals of team 1",
+            Field::Team2Score => "goals of team 2",
+        };
+        f.write_str(description)
+    }
+}
+
+// Why a line of the results couldn't be parsed.
+#[derive(Debug, PartialEq, Eq)]
+enum ParseLineErrorKind {
+    MissingField(Field),
+    ExtraField,
+    BadScore(Field, ParseIntError),
+    SameTeam,
+    EmptyTeamName(Field),
+}
+
+impl fmt::Display for ParseLineErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseLineErrorKind::MissingField(field) => write!(f, "missing the {field}"),
+            ParseLineErrorKind::ExtraField => f.write_str("more than 4 fields"),
+            ParseLineErrorKind::BadScore(field, e) => write!(f, "invalid {field}: {e}"),
+            ParseLineErrorKind::SameTeam => f.write_str("a team can't play against itself"),
+            ParseLineErrorKind::EmptyTeamName(field) => write!(f, "the {field} is empty"),
+        }
+    }
+}
+
+// An error in the results together with its line number (starting at 1).
+#[derive(Debug, PartialEq, Eq)]
+struct ParseLineError {
+    line: usize,
+    kind: ParseLineErrorKind,
+}
+
+impl fmt::Display for ParseLineError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.kind)
+    }
+}
+
+impl Error for ParseLineError {}
+
+// Parses every line of the results and attaches the line number to errors.
+fn parse_results(results: &str) -> impl Iterator<Item = Result<MatchResult<'_>, ParseLineError>> {
+    results.lines().enumerate().map(|(ind, line)| {
+        MatchResult::try_parse(line).map_err(|kind| ParseLineError {
+            line: ind + 1,
+            kind,
+        })
+    })
+}
+
+// Adding a match to the scores table would overflow the `u8` goal counters of
+// a team.
+#[derive(Debug, PartialEq, Eq)]
+struct ScoreOverflowError {
+    line: usize,
+    team_name: String,
+}
+
+impl fmt::Display for ScoreOverflowError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}: the goals of {} overflow",
+            self.line, self.team_name,
+        )
+    }
+}
+
+impl Error for ScoreOverflowError {}
+
+#[derive(Debug, PartialEq, Eq)]
+enum ScoresTableError {
+    Parse(ParseLineError),
+    Overflow(ScoreOverflowError),
+}
+
+impl ScoresTableError {
+    fn line(&self) -> usize {
+        match self {
+            ScoresTableError::Parse(e) => e.line,
+            ScoresTableError::Overflow(e) => e.line,
+        }
+    }
+}
+
+impl From<ParseLineError> for ScoresTableError {
+    fn from(err: ParseLineError) -> Self {
+        Self::Parse(err)
+    }
+}
+
+impl From<ScoreOverflowError> for ScoresTableError {
+    fn from(err: ScoreOverflowError) -> Self {
+        Self::Overflow(err)
+    }
+}
+
+impl fmt::Display for ScoresTableError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScoresTableError::Parse(e) => e.fmt(f),
+            ScoresTableError::Overflow(e) => e.fmt(f),
+        }
+    }
+}
+
+impl Error for ScoresTableError {}
+
+impl TeamScores {
+    // Returns `None` instead of overflowing.
+    fn checked_add(&self, scored: u8, conceded: u8) -> Option<Self> {
+        Some(Self {
+            goals_scored: self.goals_scored.checked_add(scored)?,
+            goals_conceded: self.goals_conceded.checked_add(conceded)?,
+        })
+    }
+}
+
+// Adds the goals of a match to the scores table. If one of the two teams would
+// overflow, the table isn't changed and the name of that team is returned.
+fn checked_add_to_scores_table<'a>(
+    scores: &mut HashMap<&'a str, TeamScores>,
+    result: &MatchResult<'a>,
+) -> Result<(), &'a str> {
+    let checked_add = |scores: &HashMap<&str, TeamScores>, team_name, scored, conceded| {
+        scores
+            .get(team_name)
+            .unwrap_or(&TeamScores::default())
+            .checked_add(scored, conceded)
+            .ok_or(team_name)
+    };
+
+    let team_1 = checked_add(
+        scores,
+        result.team_1_name,
+        result.team_1_score,
+        result.team_2_score,
+    )?;
+    let team_2 = checked_add(
+        scores,
+        result.team_2_name,
+        result.team_2_score,
+        result.team_1_score,
+    )?;
+
+    scores.insert(result.team_1_name, team_1);
+    scores.insert(result.team_2_name, team_2);
+
+    Ok(())
+}
+
+// Parses a line and adds it to the scores table.
+fn add_line_to_scores_table<'a>(
+    scores: &mut HashMap<&'a str, TeamScores>,
+    line: usize,
+    result: Result<MatchResult<'a>, ParseLineError>,
+) -> Result<(), ScoresTableError> {
+    checked_add_to_scores_table(scores, &result?).map_err(|team_name| {
+        ScoresTableError::from(ScoreOverflowError {
+            line,
+            team_name: team_name.to_string(),
+        })
+    })
+}
+
+// Like `build_scores_table`, but stops at the first invalid line or overflow
+// and returns its error instead of panicking.
+fn try_build_scores_table(results: &str) -> Result<HashMap<&str, TeamScores>, ScoresTableError> {
+    let mut scores = HashMap::new();
+
+    for (ind, result) in parse_results(results).enumerate() {
+        add_line_to_scores_table(&mut scores, ind + 1, result)?;
+    }
+
+    Ok(scores)
+}
+
+// Skips invalid or overflowing lines and returns their errors next to the
+// scores table of the remaining lines.
+fn build_scores_table_lenient(results: &str) -> (HashMap<&str, TeamScores>, Vec<ScoresTableError>) {
+    let mut scores = HashMap::new();
+    let mut errors = Vec::new();
+
+    for (ind, result) in parse_results(results).enumerate() {
+        if let Err(e) = add_line_to_scores_table(&mut scores, ind + 1, result) {
+            errors.push(e);
+        }
+    }
+
+    (scores, errors)
+}
+
+// A league keeps every match it has seen so that head-to-head records can be
+// used as a tiebreaker.
+struct League<'a> {
+    points_system: PointsSystem,
+    matches: Vec<MatchResult<'a>>,
+    standings: HashMap<&'a str, Standing>,
+}
+
+impl<'a> League<'a> {
+    fn new(points_system: PointsSystem) -> Self {
+        Self {
+            points_system,
+            matches: Vec::new(),
+            standings: HashMap::new(),
+        }
+    }
+
+    fn from_results(results: &'a str, points_system: PointsSystem) -> Self {
+        let mut league = Self::new(points_system);
+        for line in results.lines() {
+            league.record(MatchResult::parse(line));
+        }
+        league
+    }
+
+    fn try_from_results(
+        results: &'a str,
+        points_system: PointsSystem,
+    ) -> Result<Self, ParseLineError> {
+        let mut league = Self::new(points_system);
+        for result in parse_results(results) {
+            league.record(result?);
+        }
+        Ok(league)
+    }
+
+    fn record(&mut self, result: MatchResult<'a>) {
+        self.standings
+            .entry(result.team_1_name)
+            .or_default()
+            .record(result.team_1_score, result.team_2_score, self.points_system);
+        self.standings
+            .entry(result.team_2_name)
+            .or_default()
+            .record(result.team_2_score, result.team_1_score, self.points_system);
+        self.matches.push(result);
+    }
+
+    fn standing(&self, team_name: &str) -> Option<&Standing> {
+        self.standings.get(team_name)
+    }
+
+    // The points `team_name` earned in the matches played only against the
+    // other teams in `group`.
+    fn head_to_head_points(&self, team_name: &str, group: &[&str]) -> u32 {
+        let mut head_to_head = Standing::default();
+
+        for result in self
+            .matches
+            .iter()
+            .filter(|result| result.is_between(group))
+        {
+            if result.team_1_name == team_name {
+                head_to_head.record(result.team_1_score, result.team_2_score, self.points_system);
+            } else if result.team_2_name == team_name {
+                head_to_head.record(result.team_2_score, result.team_1_score, self.points_system);
+            }
+        }
+
+        head_to_head.points
+    }
+
+    // The league table from first to last place. Ties are broken by points,
+    // goal difference, goals scored, head-to-head points and finally by name.
+    fn table(&self) -> Vec<(&'a str, &Standing)> {
+        let mut table: Vec<_> = self
+            .standings
+            .iter()
+            .map(|(team_name, standing)| (*team_name, standing))
+            .collect();
+
+        let overall_key = |standing: &Standing| {
+            (
+                standing.points,
+                standing.goal_difference(),
+                standing.goals_scored,
+            )
+        };
+
+        table.sort_by(|(name_a, a), (name_b, b)| {
+            overall_key(b)
+                .cmp(&overall_key(a))
+                .then_with(|| name_a.cmp(name_b))
+        });
+
+        // Teams that are still tied are only sorted by name so far. Break the
+        // ties with a mini-league of the matches between the tied teams.
+        let mut start = 0;
+        while start < table.len() {
+            let key = overall_key(table[start].1);
+            let len = table[start..]
+                .iter()
+                .take_while(|(_, standing)| overall_key(standing) == key)
+                .count();
+
+            if len > 1 {
+                let group = &mut table[start..start + len];
+                let team_names: Vec<&str> = group.iter().map(|(team_name, _)| *team_name).collect();
+                // The sort is stable, so the order by name is kept for teams
+                // that are tied on head-to-head points too.
+                group.sort_by_key(|(team_name, _)| {
+                    std::cmp::Reverse(self.head_to_head_points(team_name, &team_names))
+                });
+            }
+
+            start += len;
+        }
+
+        table
+    }
+}
+
 fn main() {
     // You can optionally experiment here.
 }
@@ -80,4 +539,359 @@ England,Spain,1,0";
         assert_eq!(team.goals_scored, 0);
         assert_eq!(team.goals_conceded, 3);
     }
+
+    #[test]
+    fn league_standing() {
+        let league = League::from_results(RESULTS, PointsSystem::default());
+        let england = league.standing("England").unwrap();
+        assert_eq!(
+            *england,
+            Standing {
+                played: 3,
+                wins: 2,
+                draws: 0,
+                losses: 1,
+                goals_scored: 6,
+                goals_conceded: 4,
+                points: 6,
+            },
+        );
+        assert_eq!(england.goal_difference(), 2);
+        assert_eq!(league.standing("Spain").unwrap().goal_difference(), -3);
+        assert!(league.standing("Brazil").is_none());
+    }
+
+    #[test]
+    fn league_table_order() {
+        let league = League::from_results(RESULTS, PointsSystem::default());
+        let team_names: Vec<&str> = league.table().into_iter().map(|(name, _)| name).collect();
+        assert_eq!(
+            team_names,
+            ["England", "Poland", "Germany", "France", "Italy", "Spain"],
+        );
+    }
+
+    #[test]
+    fn custom_points_system() {
+        let points_system = PointsSystem {
+            win: 2,
+            draw: 1,
+            loss: 0,
+        };
+        let league = League::from_results("Spain,Italy,1,1\nItaly,France,0,3", points_system);
+        assert_eq!(league.standing("Spain").unwrap().points, 1);
+        assert_eq!(league.standing("Italy").unwrap().points, 1);
+        assert_eq!(league.standing("France").unwrap().points, 2);
+        assert_eq!(league.standing("France").unwrap().draws, 0);
+        assert_eq!(league.standing("Spain").unwrap().draws, 1);
+    }
+
+    #[test]
+    fn head_to_head_tiebreaker() {
+        // Alpha and Bravo are tied on points, goal difference and goals
+        // scored. Bravo won their match, so it is placed above Alpha.
+        let results = "Bravo,Alpha,1,0\nAlpha,Charlie,1,0\nDelta,Bravo,1,0";
+        let league = League::from_results(results, PointsSystem::default());
+        let team_names: Vec<&str> = league.table().into_iter().map(|(name, _)| name).collect();
+        assert_eq!(team_names, ["Delta", "Bravo", "Alpha", "Charlie"]);
+    }
+
+    #[test]
+    fn name_tiebreaker() {
+        // Every team won once and lost once by the same score, so only the
+        // name is left to order them.
+        let results = "Charlie,Alpha,1,0\nAlpha,Bravo,1,0\nBravo,Charlie,1,0";
+        let league = League::from_results(results, PointsSystem::default());
+        let team_names: Vec<&str> = league.table().into_iter().map(|(name, _)| name).collect();
+        assert_eq!(team_names, ["Alpha", "Bravo", "Charlie"]);
+    }
+
+    // A double round-robin season with pseudo-random scores between 0 and 4.
+    fn generate_season(n_teams: usize, mut seed: u64) -> String {
+        let mut next_score = || {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (seed >> 33) % 5
+        };
+
+        let mut results = Vec::new();
+        for home in 0..n_teams {
+            for away in (0..n_teams).filter(|&away| away != home) {
+                results.push(format!(
+                    "Team {home:02},Team {away:02},{},{}",
+                    next_score(),
+                    next_score(),
+                ));
+            }
+        }
+        results.join("\n")
+    }
+
+    #[test]
+    fn generated_seasons() {
+        for (n_teams, seed) in [(2, 1), (6, 2), (10, 3), (20, 4)] {
+            let results = generate_season(n_teams, seed);
+            let points_system = PointsSystem::default();
+            let league = League::from_results(&results, points_system);
+            let table = league.table();
+            let n_matches = (n_teams * (n_teams - 1)) as u32;
+
+            assert_eq!(table.len(), n_teams);
+            assert_eq!(league.matches.len() as u32, n_matches);
+
+            let sum = |field: fn(&Standing) -> u32| -> u32 {
+                table.iter().map(|(_, standing)| field(standing)).sum()
+            };
+            let wins = sum(|standing| standing.wins);
+            let draws = sum(|standing| standing.draws);
+            assert_eq!(wins, sum(|standing| standing.losses));
+            assert_eq!(draws % 2, 0);
+            assert_eq!(wins + draws / 2, n_matches);
+            assert_eq!(
+                sum(|standing| standing.points),
+                wins * points_system.win + draws * points_system.draw,
+            );
+            assert_eq!(
+                sum(|standing| standing.goals_scored),
+                sum(|standing| standing.goals_conceded),
+            );
+
+            for (_, standing) in &table {
+                assert_eq!(standing.played as usize, 2 * (n_teams - 1));
+            }
+
+            for pair in table.windows(2) {
+                let (_, a) = pair[0];
+                let (_, b) = pair[1];
+                assert!(
+                    (a.points, a.goal_difference(), a.goals_scored)
+                        >= (b.points, b.goal_difference(), b.goals_scored)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn generated_season_matches_scores_table() {
+        let results = generate_season(6, 42);
+        let scores = build_scores_table(&results);
+        let league = League::from_results(&results, PointsSystem::default());
+
+        for (team_name, team) in &scores {
+            let standing = league.standing(team_name).unwrap();
+            assert_eq!(standing.goals_scored, u32::from(team.goals_scored));
+            assert_eq!(standing.goals_conceded, u32::from(team.goals_conceded));
+        }
+    }
+
+    #[test]
+    fn try_build_scores() {
+        let scores = try_build_scores_table(RESULTS).unwrap();
+        let team = scores.get("England").unwrap();
+        assert_eq!(team.goals_scored, 6);
+        assert_eq!(team.goals_conceded, 4);
+        assert_eq!(scores.len(), 6);
+    }
+
+    #[test]
+    fn try_build_scores_ignores_whitespace() {
+        let scores = try_build_scores_table("England, France , 4,2 ").unwrap();
+        assert_eq!(scores.get("France").unwrap().goals_scored, 2);
+    }
+
+    #[test]
+    fn missing_field() {
+        let results = "England,France,4,2\nEngland,France,4";
+        assert_eq!(
+            try_build_scores_table(results).err(),
+            Some(ScoresTableError::Parse(ParseLineError {
+                line: 2,
+                kind: ParseLineErrorKind::MissingField(Field::Team2Score),
+            })),
+        );
+        assert_eq!(
+            MatchResult::try_parse(""),
+            Err(ParseLineErrorKind::MissingField(Field::Team2Name)),
+        );
+    }
+
+    #[test]
+    fn extra_field() {
+        assert_eq!(
+            MatchResult::try_parse("England,France,4,2,1"),
+            Err(ParseLineErrorKind::ExtraField),
+        );
+    }
+
+    #[test]
+    fn bad_score() {
+        let err = try_build_scores_table("Spain,Italy,x,1").unwrap_err();
+        assert_eq!(err.line(), 1);
+        assert!(matches!(
+            err,
+            ScoresTableError::Parse(ParseLineError {
+                kind: ParseLineErrorKind::BadScore(Field::Team1Score, _),
+                ..
+            }),
+        ));
+        assert!(matches!(
+            MatchResult::try_parse("Spain,Italy,1,256"),
+            Err(ParseLineErrorKind::BadScore(Field::Team2Score, _)),
+        ));
+        assert!(matches!(
+            MatchResult::try_parse("Spain,Italy,-1,0"),
+            Err(ParseLineErrorKind::BadScore(Field::Team1Score, _)),
+        ));
+    }
+
+    #[test]
+    fn same_team() {
+        assert_eq!(
+            MatchResult::try_parse("Spain,Spain,1,1"),
+            Err(ParseLineErrorKind::SameTeam),
+        );
+    }
+
+    #[test]
+    fn empty_team_name() {
+        assert_eq!(
+            MatchResult::try_parse(",Spain,1,1"),
+            Err(ParseLineErrorKind::EmptyTeamName(Field::Team1Name)),
+        );
+        assert_eq!(
+            MatchResult::try_parse("Spain, ,1,1"),
+            Err(ParseLineErrorKind::EmptyTeamName(Field::Team2Name)),
+        );
+    }
+
+    #[test]
+    fn error_display() {
+        let err = try_build_scores_table("England,France,4,2\nSpain,Italy,x,1").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2: invalid goals of team 1: invalid digit found in string",
+        );
+        let err = try_build_scores_table("England,France").unwrap_err();
+        assert_eq!(err.to_string(), "line 1: missing the goals of team 1");
+    }
+
+    #[test]
+    fn lenient_scores_table() {
+        let results = "England,France,4,2
+England,France,4
+France,Italy,3,1
+Spain,Italy,x,1
+Spain,Spain,1,1
+Poland,Spain,2,0";
+        let (scores, errors) = build_scores_table_lenient(results);
+
+        assert_eq!(
+            errors
+                .iter()
+                .map(ScoresTableError::line)
+                .collect::<Vec<_>>(),
+            [2, 4, 5]
+        );
+        assert_eq!(scores.len(), 5);
+        let france = scores.get("France").unwrap();
+        assert_eq!(france.goals_scored, 5);
+        assert_eq!(france.goals_conceded, 5);
+        assert_eq!(scores.get("Spain").unwrap().goals_conceded, 2);
+    }
+
+    #[test]
+    fn try_league() {
+        let league = League::try_from_results(RESULTS, PointsSystem::default()).unwrap();
+        assert_eq!(league.standing("England").unwrap().points, 6);
+
+        let err =
+            League::try_from_results("England,France,4,2\nFrance,Italy", PointsSystem::default())
+                .err()
+                .unwrap();
+        assert_eq!(err.line, 2);
+    }
+
+    // `n_matches` matches won 5:0 by `winner` against a different team each.
+    fn repeated_wins(winner: &str, n_matches: usize) -> String {
+        (0..n_matches)
+            .map(|ind| format!("{winner},Team {ind:02},5,0"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn checked_add_team_scores() {
+        let team = TeamScores {
+            goals_scored: 250,
+            goals_conceded: 0,
+        };
+        let team = team.checked_add(5, 255).unwrap();
+        assert_eq!(team.goals_scored, 255);
+        assert_eq!(team.goals_conceded, 255);
+        assert!(team.checked_add(1, 0).is_none());
+        assert!(team.checked_add(0, 1).is_none());
+        assert!(team.checked_add(0, 0).is_some());
+    }
+
+    #[test]
+    fn score_overflow() {
+        // 51 * 5 = 255 still fits into a `u8`, the 52nd match doesn't.
+        let results = repeated_wins("Spain", 52);
+        assert_eq!(
+            try_build_scores_table(&results).err(),
+            Some(ScoresTableError::Overflow(ScoreOverflowError {
+                line: 52,
+                team_name: String::from("Spain"),
+            })),
+        );
+
+        let scores = try_build_scores_table(&results[..results.rfind('\n').unwrap()]).unwrap();
+        assert_eq!(scores.get("Spain").unwrap().goals_scored, 255);
+    }
+
+    #[test]
+    fn score_overflow_of_second_team() {
+        let results = (0..52)
+            .map(|ind| format!("Team {ind:02},Spain,1,5"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let err = try_build_scores_table(&results).unwrap_err();
+        assert_eq!(err.to_string(), "line 52: the goals of Spain overflow");
+    }
+
+    #[test]
+    fn lenient_skips_overflowing_matches() {
+        let mut results = repeated_wins("Spain", 53);
+        results.push_str("\nItaly,France,1,0");
+        let (scores, errors) = build_scores_table_lenient(&results);
+
+        assert_eq!(
+            errors
+                .iter()
+                .map(ScoresTableError::line)
+                .collect::<Vec<_>>(),
+            [52, 53],
+        );
+        // The overflowing matches were skipped for both teams.
+        assert_eq!(scores.get("Spain").unwrap().goals_scored, 255);
+        assert!(!scores.contains_key("Team 51"));
+        assert!(!scores.contains_key("Team 52"));
+        assert_eq!(scores.get("Italy").unwrap().goals_scored, 1);
+    }
+
+    #[test]
+    fn full_season_overflow() {
+        // Every team plays 158 matches with 2 goals on average per match.
+        let results = generate_season(80, 5);
+        let err = try_build_scores_table(&results).unwrap_err();
+        assert!(matches!(err, ScoresTableError::Overflow(_)));
+
+        // The league counts with `u32` and doesn't overflow.
+        let league = League::try_from_results(&results, PointsSystem::default()).unwrap();
+        assert!(league
+            .table()
+            .iter()
+            .any(|(_, standing)| standing.goals_scored > u32::from(u8::MAX)));
+    }
 }
@@ -1,13 +1,38 @@
-#[derive(Debug, PartialEq, Eq)]
+use std::collections::HashMap;
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 enum DivisionError {
     // Example: 42 / 0
     DivideByZero,
-    // Only case for `i64`: `i64::MIN / -1` because the result is `i64::MAX + 1`
+    // Only cases for `i64`: `i64::MIN / -1` and `i64::MIN % -1` because the
+    // quotient is `i64::MAX + 1`
     IntegerOverflow,
     // Example: 5 / 2 = 2.5
     NotDivisible,
+    // Example: i64::MAX + 1
+    AddOverflow,
+    // Example: i64::MIN - 1 or -i64::MIN
+    SubOverflow,
+    // Example: i64::MAX * 2
+    MulOverflow,
+}
+
+impl fmt::Display for DivisionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::DivideByZero => "division by zero",
+            Self::IntegerOverflow => "integer overflow",
+            Self::NotDivisible => "not divisible",
+            Self::AddOverflow => "addition overflow",
+            Self::SubOverflow => "subtraction overflow",
+            Self::MulOverflow => "multiplication overflow",
+        })
+    }
 }
 
+impl std::error::Error for DivisionError {}
+
 fn divide(a: i64, b: i64) -> Result<i64, DivisionError> {
     if b == 0 {
         return Err(DivisionError::DivideByZero);
@@ -41,6 +66,384 @@ fn list_of_results() -> Vec<Result<i64, DivisionError>> {
     division_results.collect()
 }
 
+// Lazily divides each numerator by the divisor at the same position. Use
+// `iter::repeat` to divide all numerators by the same divisor.
+//
+// Like `zip`, this stops at the end of the shorter input without an error: the
+// extra numerators or divisors are ignored. Check the lengths first if they
+// must match.
+fn divide_pairs<N, D>(
+    numerators: N,
+    divisors: D,
+) -> impl Iterator<Item = Result<i64, DivisionError>>
+where
+    N: IntoIterator<Item = i64>,
+    D: IntoIterator<Item = i64>,
+{
+    numerators
+        .into_iter()
+        .zip(divisors)
+        .map(|(numerator, divisor)| divide(numerator, divisor))
+}
+
+// Returns all quotients or the first error. Stops dividing at the first error.
+// Mismatched lengths are truncated like in `divide_pairs`.
+fn try_divide_all<N, D>(numerators: N, divisors: D) -> Result<Vec<i64>, DivisionError>
+where
+    N: IntoIterator<Item = i64>,
+    D: IntoIterator<Item = i64>,
+{
+    divide_pairs(numerators, divisors).collect()
+}
+
+fn divide_all<N, D>(numerators: N, divisors: D) -> Vec<Result<i64, DivisionError>>
+where
+    N: IntoIterator<Item = i64>,
+    D: IntoIterator<Item = i64>,
+{
+    divide_pairs(numerators, divisors).collect()
+}
+
+fn summarize_divisions<N, D>(numerators: N, divisors: D) -> DivisionSummary
+where
+    N: IntoIterator<Item = i64>,
+    D: IntoIterator<Item = i64>,
+{
+    divide_pairs(numerators, divisors).collect()
+}
+
+// Counts the results of a batch of divisions without storing the quotients.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct DivisionSummary {
+    successes: usize,
+    errors: HashMap<DivisionError, usize>,
+    // The positions of the failed divisions in the batch.
+    failed_indexes: Vec<usize>,
+}
+
+impl DivisionSummary {
+    fn total(&self) -> usize {
+        self.successes + self.failed_indexes.len()
+    }
+
+    fn failures(&self) -> usize {
+        self.failed_indexes.len()
+    }
+
+    fn count(&self, error: DivisionError) -> usize {
+        self.errors.get(&error).copied().unwrap_or(0)
+    }
+}
+
+// Allows summarizing any iterator of division results, e.g. with
+// `divide_pairs(…).collect::<DivisionSummary>()`.
+impl FromIterator<Result<i64, DivisionError>> for DivisionSummary {
+    fn from_iter<I: IntoIterator<Item = Result<i64, DivisionError>>>(results: I) -> Self {
+        let mut summary = Self::default();
+        for (index, result) in results.into_iter().enumerate() {
+            match result {
+                Ok(_) => summary.successes += 1,
+                Err(e) => {
+                    *summary.errors.entry(e).or_default() += 1;
+                    summary.failed_indexes.push(index);
+                }
+            }
+        }
+        summary
+    }
+}
+
+// An evaluator for integer expressions like `-(7 + 2) * 3 % 5` with `+ - * / %`
+// and parentheses. `/` is evaluated with `divide`, so it also fails if the
+// division has a remainder. All operations are checked.
+mod expression {
+    use super::{divide, DivisionError};
+    use std::fmt;
+    use std::ops::Range;
+
+    #[derive(Debug, PartialEq, Eq)]
+    pub enum ExprErrorKind {
+        Arithmetic(DivisionError),
+        UnexpectedChar(char),
+        // A token that doesn't fit at its position, e.g. the `)` in `1 + )`.
+        UnexpectedToken,
+        UnexpectedEnd,
+        NumberOutOfRange,
+        // More than `MAX_DEPTH` nested parentheses.
+        TooDeeplyNested,
+    }
+
+    // An error and the span (in bytes) of the source that caused it. For
+    // arithmetic errors, that is the whole failing operation including its
+    // operands.
+    #[derive(Debug, PartialEq, Eq)]
+    pub struct ExprError {
+        pub span: Range<usize>,
+        pub kind: ExprErrorKind,
+    }
+
+    impl fmt::Display for ExprError {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match &self.kind {
+                ExprErrorKind::Arithmetic(e) => e.fmt(f)?,
+                ExprErrorKind::UnexpectedChar(c) => write!(f, "unexpected character `{c}`")?,
+                ExprErrorKind::UnexpectedToken => f.write_str("unexpected token")?,
+                ExprErrorKind::UnexpectedEnd => f.write_str("unexpected end of expression")?,
+                ExprErrorKind::NumberOutOfRange => f.write_str("number out of range")?,
+                ExprErrorKind::TooDeeplyNested => f.write_str("too deeply nested")?,
+            }
+            write!(f, " at {}..{}", self.span.start, self.span.end)
+        }
+    }
+
+    impl std::error::Error for ExprError {}
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    enum Op {
+        Add,
+        Sub,
+        Mul,
+        Div,
+        Rem,
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    enum Token<'a> {
+        Number(&'a str),
+        Op(Op),
+        OpenParen,
+        CloseParen,
+    }
+
+    fn tokenize(source: &str) -> Result<Vec<(Token<'_>, Range<usize>)>, ExprError> {
+        let mut tokens = Vec::new();
+        let mut chars = source.char_indices().peekable();
+
+        while let Some((start, c)) = chars.next() {
+            let token = match c {
+                c if c.is_whitespace() => continue,
+                '0'..='9' => {
+                    let mut end = start + 1;
+                    while let Some((ind, '0'..='9')) = chars.peek() {
+                        end = ind + 1;
+                        chars.next();
+                    }
+                    tokens.push((Token::Number(&source[start..end]), start..end));
+                    continue;
+                }
+                '+' => Token::Op(Op::Add),
+                '-' => Token::Op(Op::Sub),
+                '*' => Token::Op(Op::Mul),
+                '/' => Token::Op(Op::Div),
+                '%' => Token::Op(Op::Rem),
+                '(' => Token::OpenParen,
+                ')' => Token::CloseParen,
+                c => {
+                    return Err(ExprError {
+                        span: start..start + c.len_utf8(),
+                        kind: ExprErrorKind::UnexpectedChar(c),
+                    })
+                }
+            };
+            tokens.push((token, start..start + 1));
+        }
+
+        Ok(tokens)
+    }
+
+    // The maximum number of nested parentheses. The parser recurses into
+    // parentheses, so this keeps it from overflowing the stack.
+    pub const MAX_DEPTH: usize = 256;
+
+    // A parsed and evaluated (sub)expression and its span in the source. It is
+    // evaluated while parsing instead of building a tree, which would need
+    // recursion to evaluate and drop long chains like `1 + 1 + … + 1`. An
+    // arithmetic error is kept until parsing is done, so that syntax errors
+    // are still found first.
+    struct Operand {
+        value: Result<i64, ExprError>,
+        span: Range<usize>,
+    }
+
+    impl Operand {
+        // Evaluates the operands from left to right. The span of a failing
+        // operation is attached to its error.
+        fn binary(op: Op, left: Operand, right: Operand) -> Self {
+            let span = left.span.start..right.span.end;
+            let value = match (left.value, right.value) {
+                (Err(e), _) | (_, Err(e)) => Err(e),
+                (Ok(a), Ok(b)) => match op {
+                    Op::Add => a.checked_add(b).ok_or(DivisionError::AddOverflow),
+                    Op::Sub => a.checked_sub(b).ok_or(DivisionError::SubOverflow),
+                    Op::Mul => a.checked_mul(b).ok_or(DivisionError::MulOverflow),
+                    Op::Div => divide(a, b),
+                    Op::Rem if b == 0 => Err(DivisionError::DivideByZero),
+                    // Only `i64::MIN % -1` overflows.
+                    Op::Rem => a.checked_rem(b).ok_or(DivisionError::IntegerOverflow),
+                }
+                .map_err(|kind| arithmetic_error(span.clone(), kind)),
+            };
+            Self { value, span }
+        }
+
+        // `-x` is `0 - x`.
+        fn neg(self, minus_start: usize) -> Self {
+            let span = minus_start..self.span.end;
+            let value = self.value.and_then(|value| {
+                value
+                    .checked_neg()
+                    .ok_or_else(|| arithmetic_error(span.clone(), DivisionError::SubOverflow))
+            });
+            Self { value, span }
+        }
+    }
+
+    fn arithmetic_error(span: Range<usize>, kind: DivisionError) -> ExprError {
+        ExprError {
+            span,
+            kind: ExprErrorKind::Arithmetic(kind),
+        }
+    }
+
+    struct Parser<'a> {
+        source: &'a str,
+        tokens: Vec<(Token<'a>, Range<usize>)>,
+        pos: usize,
+        // The number of currently open parentheses.
+        depth: usize,
+    }
+
+    impl<'a> Parser<'a> {
+        fn peek(&self) -> Option<Token<'a>> {
+            self.tokens.get(self.pos).map(|(token, _)| *token)
+        }
+
+        fn next(&mut self) -> Result<(Token<'a>, Range<usize>), ExprError> {
+            let token = self.tokens.get(self.pos).cloned().ok_or(ExprError {
+                span: self.source.len()..self.source.len(),
+                kind: ExprErrorKind::UnexpectedEnd,
+            })?;
+            self.pos += 1;
+            Ok(token)
+        }
+
+        // expression = term (("+" | "-") term)*
+        fn expression(&mut self) -> Result<Operand, ExprError> {
+            let mut left = self.term()?;
+            while let Some(Token::Op(op @ (Op::Add | Op::Sub))) = self.peek() {
+                self.pos += 1;
+                left = Operand::binary(op, left, self.term()?);
+            }
+            Ok(left)
+        }
+
+        // term = unary (("*" | "/" | "%") unary)*
+        fn term(&mut self) -> Result<Operand, ExprError> {
+            let mut left = self.unary()?;
+            while let Some(Token::Op(op @ (Op::Mul | Op::Div | Op::Rem))) = self.peek() {
+                self.pos += 1;
+                left = Operand::binary(op, left, self.unary()?);
+            }
+            Ok(left)
+        }
+
+        // unary = "-"* (number | "(" expression ")")
+        fn unary(&mut self) -> Result<Operand, ExprError> {
+            // The starts of the minus signs in front of the operand.
+            let mut minus_starts = Vec::new();
+            let operand = loop {
+                let (token, span) = self.next()?;
+                match token {
+                    Token::Op(Op::Sub) => {
+                        // A minus directly in front of a number is part of the
+                        // number, so that `-9223372036854775808` is `i64::MIN`.
+                        if let Some((Token::Number(_), number_span)) = self.tokens.get(self.pos) {
+                            if number_span.start == span.end {
+                                let span = span.start..number_span.end;
+                                self.pos += 1;
+                                break self.number(span)?;
+                            }
+                        }
+                        minus_starts.push(span.start);
+                    }
+                    Token::Number(_) => break self.number(span)?,
+                    Token::OpenParen => break self.parenthesized(span)?,
+                    Token::Op(_) | Token::CloseParen => return Err(unexpected_token(span)),
+                }
+            };
+
+            Ok(minus_starts
+                .into_iter()
+                .rev()
+                .fold(operand, |operand, minus_start| operand.neg(minus_start)))
+        }
+
+        // The expression after the `(` at `open_span` up to its `)`.
+        fn parenthesized(&mut self, open_span: Range<usize>) -> Result<Operand, ExprError> {
+            if self.depth == MAX_DEPTH {
+                return Err(ExprError {
+                    span: open_span,
+                    kind: ExprErrorKind::TooDeeplyNested,
+                });
+            }
+
+            self.depth += 1;
+            let inner = self.expression()?;
+            self.depth -= 1;
+            match self.next()? {
+                (Token::CloseParen, close_span) => {
+                    let span = open_span.start..close_span.end;
+                    let mut value = inner.value;
+                    // The parentheses belong to the failing operation.
+                    if let Err(e) = &mut value {
+                        if e.span == inner.span {
+                            e.span = span.clone();
+                        }
+                    }
+                    Ok(Operand { value, span })
+                }
+                (_, span) => Err(unexpected_token(span)),
+            }
+        }
+
+        fn number(&self, span: Range<usize>) -> Result<Operand, ExprError> {
+            match self.source[span.clone()].parse() {
+                Ok(number) => Ok(Operand {
+                    value: Ok(number),
+                    span,
+                }),
+                Err(_) => Err(ExprError {
+                    span,
+                    kind: ExprErrorKind::NumberOutOfRange,
+                }),
+            }
+        }
+    }
+
+    fn unexpected_token(span: Range<usize>) -> ExprError {
+        ExprError {
+            span,
+            kind: ExprErrorKind::UnexpectedToken,
+        }
+    }
+
+    pub fn evaluate(source: &str) -> Result<i64, ExprError> {
+        let mut parser = Parser {
+            source,
+            tokens: tokenize(source)?,
+            pos: 0,
+            depth: 0,
+        };
+
+        let expression = parser.expression()?;
+        if let Some((_, span)) = parser.tokens.get(parser.pos) {
+            return Err(unexpected_token(span.clone()));
+        }
+
+        expression.value
+    }
+}
+
 fn main() {
     // You can optionally experiment here.
 }
@@ -48,6 +451,7 @@ fn main() {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::iter;
 
     #[test]
     fn test_success() {
@@ -83,4 +487,257 @@ mod tests {
     fn test_list_of_results() {
         assert_eq!(list_of_results(), [Ok(1), Ok(11), Ok(1426), Ok(3)]);
     }
+
+    #[test]
+    fn batch_first_error() {
+        assert_eq!(try_divide_all([6, -8, 0], [3, 2, 5]), Ok(vec![2, -4, 0]));
+        assert_eq!(
+            try_divide_all([6, 7, 1], [3, 2, 0]),
+            Err(DivisionError::NotDivisible),
+        );
+        assert_eq!(try_divide_all([], iter::repeat(0)), Ok(vec![]));
+
+        // No division after the first error is evaluated, so an infinite
+        // input is fine.
+        assert_eq!(
+            try_divide_all((0..).map(|n| 10 - n), iter::repeat(5)),
+            Err(DivisionError::NotDivisible),
+        );
+    }
+
+    #[test]
+    fn batch_all_results() {
+        assert_eq!(
+            divide_all([4, 4, i64::MIN, 5], [2, 0, -1, 2]),
+            [
+                Ok(2),
+                Err(DivisionError::DivideByZero),
+                Err(DivisionError::IntegerOverflow),
+                Err(DivisionError::NotDivisible),
+            ],
+        );
+    }
+
+    #[test]
+    fn batch_mismatched_lengths() {
+        // The shorter input decides the length, the rest is ignored.
+        assert_eq!(divide_all([1, 2, 3], [1]), [Ok(1)]);
+        assert_eq!(divide_all([4], [2, 0, 0]), [Ok(2)]);
+        assert_eq!(divide_all([], [1, 2]), []);
+        // Even if the ignored divisions would fail.
+        assert_eq!(try_divide_all([6, 1], [3]), Ok(vec![2]));
+        assert_eq!(try_divide_all([6], [3, 0]), Ok(vec![2]));
+        assert_eq!(summarize_divisions([1, 2, 3], [1, 0]).total(), 2);
+    }
+
+    #[test]
+    fn batch_summary() {
+        let summary = summarize_divisions([1, 2, 3, 4, 5, 8, i64::MIN], [0, 2, 2, 0, 1, 4, -1]);
+        assert_eq!(summary.total(), 7);
+        assert_eq!(summary.successes, 3);
+        assert_eq!(summary.failures(), 4);
+        assert_eq!(summary.count(DivisionError::DivideByZero), 2);
+        assert_eq!(summary.count(DivisionError::NotDivisible), 1);
+        assert_eq!(summary.count(DivisionError::IntegerOverflow), 1);
+        assert_eq!(summary.count(DivisionError::AddOverflow), 0);
+        assert_eq!(summary.failed_indexes, [0, 2, 3, 6]);
+
+        assert_eq!(summarize_divisions([], []), DivisionSummary::default());
+    }
+
+    #[test]
+    fn batch_on_iterators() {
+        // Neither input is collected before dividing.
+        let divisors = (1..).filter(|n| n % 2 == 1);
+        let quotients: Vec<i64> = divide_pairs((1..=5).map(|n| n * 15), divisors)
+            .map_while(Result::ok)
+            .collect();
+        assert_eq!(quotients, [15, 10, 9]);
+
+        let summary: DivisionSummary = divide_pairs(0..100, iter::repeat(3)).collect();
+        assert_eq!(summary.successes, 34);
+        assert_eq!(summary.count(DivisionError::NotDivisible), 66);
+        assert_eq!(summary.failed_indexes[..3], [1, 2, 4]);
+    }
+
+    mod expression {
+        use super::super::expression::{evaluate, ExprError, ExprErrorKind};
+        use super::super::DivisionError;
+
+        fn arithmetic_error(
+            span: std::ops::Range<usize>,
+            kind: DivisionError,
+        ) -> Result<i64, ExprError> {
+            Err(ExprError {
+                span,
+                kind: ExprErrorKind::Arithmetic(kind),
+            })
+        }
+
+        #[test]
+        fn evaluate_expressions() {
+            assert_eq!(evaluate("42"), Ok(42));
+            assert_eq!(evaluate("1 + 2 * 3"), Ok(7));
+            assert_eq!(evaluate("(1 + 2) * 3"), Ok(9));
+            assert_eq!(evaluate("10 - 4 - 3"), Ok(3));
+            assert_eq!(evaluate("81 / 9 / 3"), Ok(3));
+            assert_eq!(evaluate("17 % 5 * 2"), Ok(4));
+            assert_eq!(evaluate("-(7 + 2) * 3 % 5"), Ok(-2));
+            assert_eq!(evaluate("--3"), Ok(3));
+            assert_eq!(evaluate("2 - -3"), Ok(5));
+            assert_eq!(evaluate(" ( ( 1 ) ) "), Ok(1));
+            assert_eq!(evaluate("-7 % 3"), Ok(-1));
+        }
+
+        #[test]
+        fn evaluate_at_the_limits() {
+            assert_eq!(evaluate("9223372036854775807"), Ok(i64::MAX));
+            assert_eq!(evaluate("-9223372036854775808"), Ok(i64::MIN));
+            assert_eq!(evaluate("-9223372036854775807 - 1"), Ok(i64::MIN));
+            assert_eq!(
+                evaluate("9223372036854775807 + -9223372036854775808"),
+                Ok(-1)
+            );
+            assert_eq!(
+                evaluate("-9223372036854775808 % -1 + 1"),
+                arithmetic_error(0..25, DivisionError::IntegerOverflow)
+            );
+        }
+
+        #[test]
+        fn arithmetic_errors_with_spans() {
+            assert_eq!(
+                evaluate("1 + 42 / 0"),
+                arithmetic_error(4..10, DivisionError::DivideByZero)
+            );
+            assert_eq!(
+                evaluate("(5 / 2)"),
+                arithmetic_error(0..7, DivisionError::NotDivisible)
+            );
+            assert_eq!(
+                evaluate("((5 / 2)) + 1"),
+                arithmetic_error(0..9, DivisionError::NotDivisible)
+            );
+            assert_eq!(
+                evaluate("5 % 0"),
+                arithmetic_error(0..5, DivisionError::DivideByZero)
+            );
+            assert_eq!(
+                evaluate("1 + (9223372036854775807 + 1)"),
+                arithmetic_error(4..29, DivisionError::AddOverflow),
+            );
+            assert_eq!(
+                evaluate("-9223372036854775808 - 1"),
+                arithmetic_error(0..24, DivisionError::SubOverflow),
+            );
+            assert_eq!(
+                evaluate("- (-9223372036854775808)"),
+                arithmetic_error(0..24, DivisionError::SubOverflow),
+            );
+            assert_eq!(
+                evaluate("2 * 4611686018427387904"),
+                arithmetic_error(0..23, DivisionError::MulOverflow),
+            );
+            assert_eq!(
+                evaluate("-9223372036854775808 / -1"),
+                arithmetic_error(0..25, DivisionError::IntegerOverflow),
+            );
+            // The left operand is evaluated first.
+            assert_eq!(
+                evaluate("1 / 0 + 2 / 0"),
+                arithmetic_error(0..5, DivisionError::DivideByZero)
+            );
+        }
+
+        #[test]
+        fn syntax_errors() {
+            let error = |span, kind| Err(ExprError { span, kind });
+
+            assert_eq!(evaluate(""), error(0..0, ExprErrorKind::UnexpectedEnd));
+            assert_eq!(evaluate("1 +"), error(3..3, ExprErrorKind::UnexpectedEnd));
+            assert_eq!(
+                evaluate("(1 + 2"),
+                error(6..6, ExprErrorKind::UnexpectedEnd)
+            );
+            assert_eq!(
+                evaluate("1 + )"),
+                error(4..5, ExprErrorKind::UnexpectedToken)
+            );
+            assert_eq!(evaluate("1 2"), error(2..3, ExprErrorKind::UnexpectedToken));
+            assert_eq!(
+                evaluate("(1))"),
+                error(3..4, ExprErrorKind::UnexpectedToken)
+            );
+            assert_eq!(evaluate("* 2"), error(0..1, ExprErrorKind::UnexpectedToken));
+            assert_eq!(
+                evaluate("1 ^ 2"),
+                error(2..3, ExprErrorKind::UnexpectedChar('^'))
+            );
+            assert_eq!(
+                evaluate("1 + é"),
+                error(4..6, ExprErrorKind::UnexpectedChar('é'))
+            );
+            assert_eq!(
+                evaluate("9223372036854775808"),
+                error(0..19, ExprErrorKind::NumberOutOfRange),
+            );
+            assert_eq!(
+                evaluate("- 9223372036854775808"),
+                error(2..21, ExprErrorKind::NumberOutOfRange),
+            );
+            // Syntax errors are found before anything is evaluated.
+            assert_eq!(
+                evaluate("1 / 0 +"),
+                error(7..7, ExprErrorKind::UnexpectedEnd)
+            );
+        }
+
+        #[test]
+        fn long_chains() {
+            assert_eq!(evaluate(&vec!["1"; 500_000].join("+")), Ok(500_000));
+            assert_eq!(evaluate(&vec!["2"; 500_000].join("*2/")), Ok(2));
+            assert_eq!(evaluate(&format!("{}1", "-".repeat(500_001))), Ok(-1));
+            assert_eq!(evaluate(&format!("{}1", "- ".repeat(500_000))), Ok(1));
+        }
+
+        #[test]
+        fn nesting_depth() {
+            use super::super::expression::MAX_DEPTH;
+
+            let nested = |depth| format!("{}1{}", "(".repeat(depth), ")".repeat(depth));
+            assert_eq!(evaluate(&nested(MAX_DEPTH)), Ok(1));
+            assert_eq!(
+                evaluate(&nested(MAX_DEPTH + 1)),
+                Err(ExprError {
+                    span: MAX_DEPTH..MAX_DEPTH + 1,
+                    kind: ExprErrorKind::TooDeeplyNested,
+                }),
+            );
+            assert_eq!(
+                evaluate(&nested(500_000)).unwrap_err().kind,
+                ExprErrorKind::TooDeeplyNested
+            );
+            // Minus signs don't count.
+            assert_eq!(
+                evaluate(&format!(
+                    "{}1{}",
+                    "-(".repeat(MAX_DEPTH),
+                    ")".repeat(MAX_DEPTH)
+                )),
+                Ok(1)
+            );
+        }
+
+        #[test]
+        fn error_display() {
+            assert_eq!(
+                evaluate("1 + 42 / 0").unwrap_err().to_string(),
+                "division by zero at 4..10"
+            );
+            assert_eq!(
+                evaluate("1 +").unwrap_err().to_string(),
+                "unexpected end of expression at 3..3"
+            );
+        }
+    }
 }
@@ -35,6 +35,224 @@ fn factorial_product(num: u64) -> u64 {
     (2..=num).product()
 }
 
+// The functions above overflow past 20! because the result doesn't fit into
+// `u64` anymore. The same 3 solutions with `BigUint` don't overflow.
+mod big {
+    use std::fmt;
+    use std::iter::Product;
+    use std::ops::{Mul, MulAssign};
+
+    // An unsigned integer of arbitrary size that only supports what the
+    // factorial functions need.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct BigUint {
+        // Base 2^64 digits, least significant first, without trailing zeros.
+        // Zero has no digits.
+        limbs: Vec<u64>,
+    }
+
+    impl From<u64> for BigUint {
+        fn from(n: u64) -> Self {
+            let limbs = if n == 0 { Vec::new() } else { vec![n] };
+            Self { limbs }
+        }
+    }
+
+    impl MulAssign<u64> for BigUint {
+        fn mul_assign(&mut self, rhs: u64) {
+            if rhs == 0 {
+                self.limbs.clear();
+                return;
+            }
+
+            let mut carry = 0;
+            for limb in &mut self.limbs {
+                // Can't overflow: (2^64 - 1)^2 + (2^64 - 1) < 2^128
+                let product = u128::from(*limb) * u128::from(rhs) + carry;
+                *limb = product as u64;
+                carry = product >> 64;
+            }
+            if carry > 0 {
+                self.limbs.push(carry as u64);
+            }
+        }
+    }
+
+    impl Mul<u64> for BigUint {
+        type Output = Self;
+
+        fn mul(mut self, rhs: u64) -> Self {
+            self *= rhs;
+            self
+        }
+    }
+
+    impl Product<u64> for BigUint {
+        fn product<I: Iterator<Item = u64>>(iter: I) -> Self {
+            iter.fold(Self::from(1), |acc, x| acc * x)
+        }
+    }
+
+    impl fmt::Display for BigUint {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            // The largest power of 10 that fits into `u64`.
+            const CHUNK: u64 = 10_u64.pow(19);
+
+            // Divide repeatedly by 10^19 to get chunks of 19 decimal digits,
+            // least significant first.
+            let mut limbs = self.limbs.clone();
+            let mut chunks = Vec::new();
+            while !limbs.is_empty() {
+                let mut remainder = 0;
+                for limb in limbs.iter_mut().rev() {
+                    let dividend = (u128::from(remainder) << 64) | u128::from(*limb);
+                    *limb = (dividend / u128::from(CHUNK)) as u64;
+                    remainder = (dividend % u128::from(CHUNK)) as u64;
+                }
+                chunks.push(remainder);
+                while limbs.last() == Some(&0) {
+                    limbs.pop();
+                }
+            }
+
+            let Some((most_significant, rest)) = chunks.split_last() else {
+                return f.pad("0");
+            };
+            let mut digits = most_significant.to_string();
+            for chunk in rest.iter().rev() {
+                digits.push_str(&format!("{chunk:019}"));
+            }
+            f.pad(&digits)
+        }
+    }
+}
+
+use big::BigUint;
+
+fn big_factorial_for(num: u64) -> BigUint {
+    let mut result = BigUint::from(1);
+
+    for x in 2..=num {
+        result *= x;
+    }
+
+    result
+}
+
+fn big_factorial_fold(num: u64) -> BigUint {
+    (2..=num).fold(BigUint::from(1), |acc, x| acc * x)
+}
+
+fn big_factorial_product(num: u64) -> BigUint {
+    (2..=num).product()
+}
+
+// Counting with `u64`. The `checked_*` functions return `None` if the result
+// doesn't fit into `u64`, the others panic in that case.
+mod combinatorics {
+    pub fn checked_factorial(n: u64) -> Option<u64> {
+        (2..=n).try_fold(1_u64, |acc, x| acc.checked_mul(x))
+    }
+
+    pub fn factorial(n: u64) -> u64 {
+        checked_factorial(n).expect("factorial overflows u64")
+    }
+
+    // `n choose k`: The number of ways to choose `k` of `n` items.
+    pub fn checked_binomial(n: u64, k: u64) -> Option<u64> {
+        if k > n {
+            return Some(0);
+        }
+        // `n choose k` = `n choose (n - k)`
+        let k = k.min(n - k);
+
+        // After step `i`, `result` is `(n - k + i) choose i`. These values
+        // increase with `i`, so they all fit if the final result fits. The
+        // product before the division is computed in `u128` where it can't
+        // overflow.
+        let mut result = 1_u64;
+        for i in 1..=k {
+            let product = u128::from(result) * u128::from(n - k + i);
+            result = u64::try_from(product / u128::from(i)).ok()?;
+        }
+        Some(result)
+    }
+
+    pub fn binomial(n: u64, k: u64) -> u64 {
+        checked_binomial(n, k).expect("binomial coefficient overflows u64")
+    }
+
+    // `nPk`: The number of ordered arrangements of `k` of `n` items.
+    pub fn checked_permutations(n: u64, k: u64) -> Option<u64> {
+        if k > n {
+            return Some(0);
+        }
+        // The factors `n - k + 1..=n`, without overflowing for `k == 0`.
+        (n - k..n)
+            .map(|x| x + 1)
+            .try_fold(1_u64, |acc, x| acc.checked_mul(x))
+    }
+
+    pub fn permutations(n: u64, k: u64) -> u64 {
+        checked_permutations(n, k).expect("number of permutations overflows u64")
+    }
+
+    // Iterates over the `k`-combinations of a slice in lexicographic order of
+    // the indexes. The items of each combination keep their order in the
+    // slice.
+    pub struct Combinations<'a, T> {
+        items: &'a [T],
+        k: usize,
+        // The indexes of the next combination or `None` if there is none.
+        indexes: Option<Vec<usize>>,
+    }
+
+    pub fn combinations<T>(items: &[T], k: usize) -> Combinations<'_, T> {
+        Combinations {
+            items,
+            k,
+            indexes: (k <= items.len()).then(|| (0..k).collect()),
+        }
+    }
+
+    impl<T> Combinations<'_, T> {
+        // The total number of combinations, including those already returned.
+        pub fn checked_total(&self) -> Option<u64> {
+            checked_binomial(self.items.len() as u64, self.k as u64)
+        }
+
+        pub fn total(&self) -> u64 {
+            self.checked_total()
+                .expect("number of combinations overflows u64")
+        }
+    }
+
+    impl<'a, T> Iterator for Combinations<'a, T> {
+        type Item = Vec<&'a T>;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            let indexes = self.indexes.as_mut()?;
+            let combination = indexes.iter().map(|&i| &self.items[i]).collect();
+
+            // Advance the rightmost index that can still move to the right
+            // and put the indexes after it directly behind it.
+            let n = self.items.len();
+            let k = self.k;
+            match (0..k).rev().find(|&i| indexes[i] < n - k + i) {
+                Some(i) => {
+                    indexes[i] += 1;
+                    for j in i + 1..k {
+                        indexes[j] = indexes[j - 1] + 1;
+                    }
+                }
+                None => self.indexes = None,
+            }
+
+            Some(combination)
+        }
+    }
+}
+
 fn main() {
     // You can optionally experiment here.
 }
@@ -69,4 +287,173 @@ mod tests {
         assert_eq!(factorial_fold(4), 24);
         assert_eq!(factorial_product(4), 24);
     }
+
+    const FACTORIAL_OF_100: &str = "93326215443944152681699238856266700490715968264381621468592963895217599993229915608941463976156518286253697920827223758251185210916864000000000000000000000000";
+
+    fn big_factorials(num: u64) -> [String; 3] {
+        [
+            big_factorial_for(num).to_string(),
+            big_factorial_fold(num).to_string(),
+            big_factorial_product(num).to_string(),
+        ]
+    }
+
+    #[test]
+    fn big_factorials_agree_with_u64() {
+        for num in 0..=20 {
+            let expected = factorial_product(num).to_string();
+            assert_eq!(big_factorials(num), [expected.as_str(); 3]);
+        }
+    }
+
+    #[test]
+    fn big_factorial_past_u64() {
+        // 21! is the first factorial that doesn't fit into `u64`.
+        assert_eq!(big_factorials(21), ["51090942171709440000"; 3]);
+        assert_eq!(big_factorials(25), ["15511210043330985984000000"; 3]);
+    }
+
+    #[test]
+    fn big_factorial_of_100() {
+        assert_eq!(FACTORIAL_OF_100.len(), 158);
+        assert_eq!(big_factorials(100), [FACTORIAL_OF_100; 3]);
+    }
+
+    #[test]
+    fn big_uint() {
+        assert_eq!(BigUint::from(0).to_string(), "0");
+        assert_eq!(BigUint::from(u64::MAX).to_string(), u64::MAX.to_string());
+        assert_eq!(
+            (BigUint::from(u64::MAX) * u64::MAX).to_string(),
+            (u128::from(u64::MAX) * u128::from(u64::MAX)).to_string(),
+        );
+        // A chunk with leading zeros in the middle.
+        assert_eq!(
+            (BigUint::from(10_u64.pow(19)) * 10).to_string(),
+            format!("1{}", "0".repeat(20))
+        );
+        let mut zero = big_factorial_for(30);
+        zero *= 0;
+        assert_eq!(zero, BigUint::from(0));
+        assert_eq!(format!("{:>4}", BigUint::from(42)), "  42");
+    }
+
+    mod combinatorics {
+        use super::super::combinatorics::*;
+
+        #[test]
+        fn factorial_at_u64_boundary() {
+            assert_eq!(checked_factorial(0), Some(1));
+            assert_eq!(checked_factorial(20), Some(2_432_902_008_176_640_000));
+            assert_eq!(checked_factorial(21), None);
+            assert_eq!(checked_factorial(u64::MAX), None);
+            assert_eq!(factorial(5), 120);
+        }
+
+        #[test]
+        #[should_panic(expected = "factorial overflows u64")]
+        fn factorial_overflow() {
+            factorial(21);
+        }
+
+        #[test]
+        fn binomial_coefficients() {
+            assert_eq!(binomial(0, 0), 1);
+            assert_eq!(binomial(5, 0), 1);
+            assert_eq!(binomial(5, 2), 10);
+            assert_eq!(binomial(5, 3), 10);
+            assert_eq!(binomial(5, 5), 1);
+            assert_eq!(binomial(5, 6), 0);
+            // Pascal's triangle
+            for n in 1..40 {
+                for k in 1..n {
+                    assert_eq!(binomial(n, k), binomial(n - 1, k - 1) + binomial(n - 1, k));
+                }
+            }
+        }
+
+        #[test]
+        fn binomial_at_u64_boundary() {
+            // `result * (n - k + i)` would overflow `u64` in between.
+            assert_eq!(checked_binomial(62, 31), Some(465_428_353_255_261_088));
+            // The largest central binomial coefficient that fits into `u64`.
+            assert_eq!(checked_binomial(67, 33), Some(14_226_520_737_620_288_370));
+            assert_eq!(checked_binomial(68, 34), None);
+            assert_eq!(checked_binomial(u64::MAX, 1), Some(u64::MAX));
+            assert_eq!(checked_binomial(u64::MAX, u64::MAX - 1), Some(u64::MAX));
+            assert_eq!(checked_binomial(u64::MAX, u64::MAX), Some(1));
+            assert_eq!(checked_binomial(u64::MAX, 2), None);
+        }
+
+        #[test]
+        #[should_panic(expected = "binomial coefficient overflows u64")]
+        fn binomial_overflow() {
+            binomial(68, 34);
+        }
+
+        #[test]
+        fn permutations_at_u64_boundary() {
+            assert_eq!(permutations(5, 0), 1);
+            assert_eq!(permutations(5, 2), 20);
+            assert_eq!(permutations(5, 6), 0);
+            assert_eq!(checked_permutations(20, 20), checked_factorial(20));
+            assert_eq!(checked_permutations(21, 21), None);
+            assert_eq!(checked_permutations(u64::MAX, 0), Some(1));
+            assert_eq!(checked_permutations(u64::MAX, 1), Some(u64::MAX));
+            assert_eq!(checked_permutations(u64::MAX, 2), None);
+            assert_eq!(
+                checked_permutations(1 << 32, 2),
+                Some(u64::MAX - (1 << 32) + 1)
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "number of permutations overflows u64")]
+        fn permutations_overflow() {
+            permutations(u64::MAX, 2);
+        }
+
+        #[test]
+        fn combinations_of_slice() {
+            let items = ['a', 'b', 'c', 'd'];
+            let pairs: Vec<String> = combinations(&items, 2)
+                .map(|c| c.into_iter().collect())
+                .collect();
+            assert_eq!(pairs, ["ab", "ac", "ad", "bc", "bd", "cd"]);
+
+            assert_eq!(
+                combinations(&items, 0).collect::<Vec<_>>(),
+                [Vec::<&char>::new()]
+            );
+            assert_eq!(
+                combinations(&items, 4).collect::<Vec<_>>(),
+                [vec![&'a', &'b', &'c', &'d']]
+            );
+            assert_eq!(combinations(&items, 5).count(), 0);
+            assert_eq!(combinations::<u8>(&[], 0).count(), 1);
+
+            for k in 0..=10 {
+                let iter = combinations(&[0; 10], k);
+                let total = iter.total();
+                assert_eq!(iter.count() as u64, total);
+                assert_eq!(total, binomial(10, k as u64));
+            }
+        }
+
+        #[test]
+        fn combinations_total_at_u64_boundary() {
+            assert_eq!(
+                combinations(&[(); 67], 33).checked_total(),
+                Some(14_226_520_737_620_288_370),
+            );
+            let items = [(); 68];
+            assert_eq!(combinations(&items, 34).checked_total(), None);
+
+            let mut iter = combinations(&items, 68);
+            assert_eq!(iter.checked_total(), Some(1));
+            iter.next();
+            assert_eq!(iter.checked_total(), Some(1));
+            assert_eq!(iter.next(), None);
+        }
+    }
 }
@@ -10,14 +10,32 @@
 //   the first element is the string, the second one is the command.
 // - The output element is going to be a vector of strings.
 
+#[derive(Clone, Debug, PartialEq, Eq)]
 enum Command {
     Uppercase,
     Trim,
     Append(usize),
+    Lowercase,
+    Replace { from: String, to: String },
+    // Reverses the order of the characters (not of the grapheme clusters).
+    Reverse,
+    // Keeps at most the given number of characters.
+    Truncate(usize),
+    Repeat(usize),
+    // Applies the commands one after the other.
+    Sequence(Vec<Command>),
+}
+
+impl Command {
+    // Applies a single command to a string.
+    fn apply(&self, string: String) -> String {
+        my_module::apply(string, self)
+    }
 }
 
 mod my_module {
     use super::Command;
+    use std::thread;
 
     // The solution with a loop. Check out `transformer_iter` for a version
     // with iterators.
@@ -26,11 +44,7 @@ mod my_module {
 
         for (string, command) in input {
             // Create the new string.
-            let new_string = match command {
-                Command::Uppercase => string.to_uppercase(),
-                Command::Trim => string.trim().to_string(),
-                Command::Append(n) => string + &"bar".repeat(n),
-            };
+            let new_string = apply(string, &command);
 
             // Push the new string to the output vector.
             output.push(new_string);
@@ -39,18 +53,309 @@ mod my_module {
         output
     }
 
+    // Applies one command using loops where needed.
+    pub fn apply(string: String, command: &Command) -> String {
+        match command {
+            Command::Uppercase => string.to_uppercase(),
+            Command::Trim => string.trim().to_string(),
+            Command::Append(n) => string + &"bar".repeat(*n),
+            Command::Lowercase => string.to_lowercase(),
+            Command::Replace { from, to } => string.replace(from.as_str(), to),
+            Command::Reverse => {
+                let mut reversed = String::with_capacity(string.len());
+                for c in string.chars().rev() {
+                    reversed.push(c);
+                }
+                reversed
+            }
+            Command::Truncate(n) => {
+                let mut string = string;
+                // `truncate` takes a byte index which has to be on a
+                // character boundary.
+                if let Some((ind, _)) = string.char_indices().nth(*n) {
+                    string.truncate(ind);
+                }
+                string
+            }
+            Command::Repeat(n) => string.repeat(*n),
+            Command::Sequence(commands) => {
+                let mut string = string;
+                for command in commands {
+                    string = apply(string, command);
+                }
+                string
+            }
+        }
+    }
+
     // Equivalent to `transform` but uses an iterator instead of a loop for
     // comparison. Don't worry, we will practice iterators later ;)
     pub fn transformer_iter(input: Vec<(String, Command)>) -> Vec<String> {
         input
             .into_iter()
-            .map(|(string, command)| match command {
-                Command::Uppercase => string.to_uppercase(),
-                Command::Trim => string.trim().to_string(),
-                Command::Append(n) => string + &"bar".repeat(n),
-            })
+            .map(|(string, command)| apply_iter(string, &command))
+            .collect()
+    }
+
+    // Like `transformer_iter`, but accepts anything that can be iterated over
+    // and returns an iterator. A string is only transformed when the next
+    // element of the returned iterator is requested, so the input doesn't have
+    // to fit into memory.
+    pub fn transformer_lazy<I>(input: I) -> impl Iterator<Item = String>
+    where
+        I: IntoIterator<Item = (String, Command)>,
+    {
+        input
+            .into_iter()
+            .map(|(string, command)| apply_iter(string, &command))
+    }
+
+    // Splits the input into `n_threads` chunks and transforms each chunk in
+    // its own thread. The output has the same order as the input.
+    pub fn transformer_parallel<I>(input: I, n_threads: usize) -> Vec<String>
+    where
+        I: IntoIterator<Item = (String, Command)>,
+    {
+        let input: Vec<_> = input.into_iter().collect();
+        let chunk_size = input.len().div_ceil(n_threads.max(1)).max(1);
+
+        let mut input = input.into_iter();
+        let mut handles = Vec::new();
+        loop {
+            let chunk: Vec<_> = input.by_ref().take(chunk_size).collect();
+            if chunk.is_empty() {
+                break;
+            }
+            handles.push(thread::spawn(move || transformer_iter(chunk)));
+        }
+
+        // Joining the handles in the order in which the threads were spawned
+        // keeps the order of the input.
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
             .collect()
     }
+
+    // Equivalent to `apply` but uses iterators instead of loops.
+    fn apply_iter(string: String, command: &Command) -> String {
+        match command {
+            Command::Uppercase => string.to_uppercase(),
+            Command::Trim => string.trim().to_string(),
+            Command::Append(n) => string + &"bar".repeat(*n),
+            Command::Lowercase => string.to_lowercase(),
+            Command::Replace { from, to } => string.replace(from.as_str(), to),
+            Command::Reverse => string.chars().rev().collect(),
+            Command::Truncate(n) => string.chars().take(*n).collect(),
+            Command::Repeat(n) => string.repeat(*n),
+            Command::Sequence(commands) => commands.iter().fold(string, apply_iter),
+        }
+    }
+}
+
+// Commands can also be written as text, e.g. "trim | uppercase | append 3".
+// The commands of such a pipeline are applied from left to right. The
+// arguments of `replace` are single words: "replace foo bar".
+mod pipeline {
+    use super::Command;
+    use std::error::Error;
+    use std::fmt;
+    use std::num::ParseIntError;
+    use std::str::FromStr;
+
+    #[derive(Debug, PartialEq, Eq)]
+    pub enum ParseErrorKind {
+        // Nothing between two `|`, before the first or after the last one.
+        EmptyCommand,
+        UnknownCommand(String),
+        MissingArgument,
+        InvalidArgument(ParseIntError),
+        UnexpectedArgument,
+        // A `|` where only a single command is allowed.
+        UnexpectedPipe,
+    }
+
+    // A syntax error and the column (in characters, starting at 1) where it
+    // was found.
+    #[derive(Debug, PartialEq, Eq)]
+    pub struct ParseError {
+        pub column: usize,
+        pub kind: ParseErrorKind,
+    }
+
+    impl fmt::Display for ParseError {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "column {}: ", self.column)?;
+            match &self.kind {
+                ParseErrorKind::EmptyCommand => f.write_str("expected a command"),
+                ParseErrorKind::UnknownCommand(name) => write!(f, "unknown command `{name}`"),
+                ParseErrorKind::MissingArgument => f.write_str("missing argument"),
+                ParseErrorKind::InvalidArgument(e) => write!(f, "invalid argument: {e}"),
+                ParseErrorKind::UnexpectedArgument => f.write_str("unexpected argument"),
+                ParseErrorKind::UnexpectedPipe => f.write_str("unexpected `|`"),
+            }
+        }
+    }
+
+    impl Error for ParseError {}
+
+    enum Token<'a> {
+        Word(&'a str),
+        Pipe,
+        End,
+    }
+
+    // Splits the input into words and pipes. Each token is paired with the
+    // column where it starts.
+    fn tokenize(input: &str) -> Vec<(usize, Token<'_>)> {
+        let mut tokens = Vec::new();
+        // The byte index and the column of the current word.
+        let mut word_start = None;
+        let mut end_column = 1;
+
+        for (column, (ind, c)) in (1..).zip(input.char_indices()) {
+            if c == '|' || c.is_whitespace() {
+                if let Some((start, start_column)) = word_start.take() {
+                    tokens.push((start_column, Token::Word(&input[start..ind])));
+                }
+                if c == '|' {
+                    tokens.push((column, Token::Pipe));
+                }
+            } else if word_start.is_none() {
+                word_start = Some((ind, column));
+            }
+            end_column = column + 1;
+        }
+
+        if let Some((start, start_column)) = word_start {
+            tokens.push((start_column, Token::Word(&input[start..])));
+        }
+        tokens.push((end_column, Token::End));
+
+        tokens
+    }
+
+    // Parses the words of one command. `end_column` is the column of the `|`
+    // or the end of the input after the command.
+    fn parse_command(words: &[(usize, &str)], end_column: usize) -> Result<Command, ParseError> {
+        let error = |column, kind| ParseError { column, kind };
+
+        let Some(&(name_column, name)) = words.first() else {
+            return Err(error(end_column, ParseErrorKind::EmptyCommand));
+        };
+
+        let argument = |ind: usize| {
+            words
+                .get(ind)
+                .copied()
+                .ok_or(error(end_column, ParseErrorKind::MissingArgument))
+        };
+        let number = |ind| {
+            let (column, argument) = argument(ind)?;
+            argument
+                .parse()
+                .map_err(|e| error(column, ParseErrorKind::InvalidArgument(e)))
+        };
+        // The counts of `append` and `repeat` are at most `u16::MAX`, so that a
+        // typo can't ask for more memory than there is.
+        let count = |ind| {
+            let (column, argument) = argument(ind)?;
+            argument
+                .parse::<u16>()
+                .map(usize::from)
+                .map_err(|e| error(column, ParseErrorKind::InvalidArgument(e)))
+        };
+
+        let (command, n_words) = match name {
+            "uppercase" => (Command::Uppercase, 1),
+            "lowercase" => (Command::Lowercase, 1),
+            "trim" => (Command::Trim, 1),
+            "reverse" => (Command::Reverse, 1),
+            "append" => (Command::Append(count(1)?), 2),
+            "truncate" => (Command::Truncate(number(1)?), 2),
+            "repeat" => (Command::Repeat(count(1)?), 2),
+            "replace" => {
+                let from = argument(1)?.1.to_string();
+                let to = argument(2)?.1.to_string();
+                (Command::Replace { from, to }, 3)
+            }
+            _ => {
+                return Err(error(
+                    name_column,
+                    ParseErrorKind::UnknownCommand(name.to_string()),
+                ))
+            }
+        };
+
+        if let Some(&(column, _)) = words.get(n_words) {
+            return Err(error(column, ParseErrorKind::UnexpectedArgument));
+        }
+
+        Ok(command)
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    pub struct Pipeline {
+        pub commands: Vec<Command>,
+    }
+
+    impl Pipeline {
+        pub fn apply(&self, string: String) -> String {
+            self.commands
+                .iter()
+                .fold(string, |string, command| command.apply(string))
+        }
+
+        // Applies the whole pipeline to each input string.
+        pub fn apply_all(&self, input: Vec<String>) -> Vec<String> {
+            input.into_iter().map(|string| self.apply(string)).collect()
+        }
+    }
+
+    impl FromStr for Pipeline {
+        type Err = ParseError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let mut commands = Vec::new();
+            let mut words = Vec::new();
+
+            for (column, token) in tokenize(s) {
+                match token {
+                    Token::Word(word) => words.push((column, word)),
+                    Token::Pipe | Token::End => {
+                        commands.push(parse_command(&words, column)?);
+                        words.clear();
+                    }
+                }
+            }
+
+            Ok(Self { commands })
+        }
+    }
+
+    impl From<Pipeline> for Command {
+        fn from(pipeline: Pipeline) -> Self {
+            Command::Sequence(pipeline.commands)
+        }
+    }
+
+    impl FromStr for Command {
+        type Err = ParseError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let mut pipeline = s.parse::<Pipeline>()?;
+
+            if pipeline.commands.len() > 1 {
+                let column = s.chars().position(|c| c == '|').unwrap_or_default() + 1;
+                return Err(ParseError {
+                    column,
+                    kind: ParseErrorKind::UnexpectedPipe,
+                });
+            }
+
+            Ok(pipeline.commands.remove(0))
+        }
+    }
 }
 
 fn main() {
@@ -63,7 +368,9 @@ mod tests {
     use super::my_module::transformer;
 
     use super::my_module::transformer_iter;
+    use super::my_module::{transformer_lazy, transformer_parallel};
     use super::Command;
+    use std::time::Instant;
 
     #[test]
     fn it_works() {
@@ -87,4 +394,371 @@ mod tests {
             );
         }
     }
+
+    fn replace(from: &str, to: &str) -> Command {
+        Command::Replace {
+            from: from.to_string(),
+            to: to.to_string(),
+        }
+    }
+
+    #[test]
+    fn new_commands() {
+        for transformer in [transformer, transformer_iter] {
+            let input = vec![
+                ("HeLLo".to_string(), Command::Lowercase),
+                ("a-b-c".to_string(), replace("-", "+")),
+                ("héllo".to_string(), Command::Reverse),
+                ("日本語です".to_string(), Command::Truncate(2)),
+                ("short".to_string(), Command::Truncate(10)),
+                ("ab".to_string(), Command::Repeat(3)),
+                ("ab".to_string(), Command::Repeat(0)),
+                (
+                    " foo ".to_string(),
+                    Command::Sequence(vec![
+                        Command::Trim,
+                        Command::Append(1),
+                        Command::Reverse,
+                        Command::Uppercase,
+                    ]),
+                ),
+                ("foo".to_string(), Command::Sequence(Vec::new())),
+            ];
+            let output = transformer(input);
+
+            assert_eq!(
+                output,
+                ["hello", "a+b+c", "olléh", "日本", "short", "ababab", "", "RABOOF", "foo",]
+            );
+        }
+    }
+
+    // Every command (with a few different arguments) including nested
+    // sequences.
+    fn all_commands() -> Vec<Command> {
+        let simple = vec![
+            Command::Uppercase,
+            Command::Lowercase,
+            Command::Trim,
+            Command::Reverse,
+            Command::Append(0),
+            Command::Append(2),
+            Command::Truncate(0),
+            Command::Truncate(1),
+            Command::Truncate(4),
+            Command::Truncate(100),
+            Command::Repeat(0),
+            Command::Repeat(3),
+            replace("l", "L"),
+            replace("ß", "ss"),
+            replace("", "-"),
+            replace(" ", ""),
+        ];
+
+        let mut commands = simple.clone();
+        for first in &simple {
+            for second in &simple {
+                commands.push(Command::Sequence(vec![first.clone(), second.clone()]));
+            }
+        }
+        commands.push(Command::Sequence(vec![
+            Command::Sequence(vec![Command::Reverse, Command::Trim]),
+            Command::Sequence(Vec::new()),
+            Command::Truncate(3),
+        ]));
+
+        commands
+    }
+
+    #[test]
+    fn transformers_agree() {
+        let strings = [
+            "",
+            "hello",
+            "  Hello World  ",
+            "Straße",
+            "héllo wörld",
+            "日本語",
+            "e\u{301}",
+            "a|b",
+        ];
+
+        let input: Vec<(String, Command)> = strings
+            .iter()
+            .flat_map(|string| {
+                all_commands()
+                    .into_iter()
+                    .map(|command| (string.to_string(), command))
+            })
+            .collect();
+
+        // `it_works` and `new_commands` check the results themselves.
+        assert_eq!(transformer(input.clone()), transformer_iter(input));
+    }
+
+    #[test]
+    fn lazy_transformer() {
+        let input = vec![
+            ("hello".to_string(), Command::Uppercase),
+            (" rome ".to_string(), Command::Trim),
+        ];
+        assert_eq!(
+            transformer_lazy(input).collect::<Vec<_>>(),
+            ["HELLO", "rome"]
+        );
+
+        // The input is infinite, so only the requested strings can be
+        // transformed.
+        let input = (0..).map(|n| (n.to_string(), Command::Append(1)));
+        let output: Vec<String> = transformer_lazy(input).skip(9).take(2).collect();
+        assert_eq!(output, ["9bar", "10bar"]);
+    }
+
+    #[test]
+    fn parallel_transformer_keeps_order() {
+        let input: Vec<_> = (0..100)
+            .map(|n| (n.to_string(), Command::Append(n % 3)))
+            .collect();
+        let expected = transformer(input.clone());
+
+        for n_threads in [0, 1, 2, 3, 7, 100, 1000] {
+            assert_eq!(transformer_parallel(input.clone(), n_threads), expected);
+        }
+        assert!(transformer_parallel(Vec::new(), 4).is_empty());
+    }
+
+    // A large input mixing cheap and expensive commands.
+    fn large_input(len: usize) -> Vec<(String, Command)> {
+        let commands = all_commands();
+        (0..len)
+            .map(|n| {
+                (
+                    format!("  Input number {n} for the Straße  "),
+                    commands[n % commands.len()].clone(),
+                )
+            })
+            .collect()
+    }
+
+    // Not a real benchmark, but run it with `--nocapture` to compare the
+    // timings of the different transformers.
+    #[test]
+    fn compare_transformers_on_large_input() {
+        let input = large_input(200_000);
+
+        let start = Instant::now();
+        let expected = transformer_iter(input.clone());
+        println!("transformer_iter: {:?}", start.elapsed());
+
+        let start = Instant::now();
+        let mut n_strings = 0;
+        for (string, expected) in transformer_lazy(input.clone()).zip(&expected) {
+            assert_eq!(&string, expected);
+            n_strings += 1;
+        }
+        println!("transformer_lazy: {:?}", start.elapsed());
+        assert_eq!(n_strings, expected.len());
+
+        let n_threads = std::thread::available_parallelism().map_or(4, |n| n.get());
+        let start = Instant::now();
+        let output = transformer_parallel(input, n_threads);
+        println!(
+            "transformer_parallel ({n_threads} threads): {:?}",
+            start.elapsed(),
+        );
+        assert_eq!(output, expected);
+    }
+
+    mod pipeline {
+        use super::super::pipeline::{ParseError, ParseErrorKind, Pipeline};
+        use super::super::Command;
+
+        fn parse_error(input: &str) -> ParseError {
+            input.parse::<Pipeline>().unwrap_err()
+        }
+
+        #[test]
+        fn parse_commands() {
+            assert_eq!("uppercase".parse(), Ok(Command::Uppercase));
+            assert_eq!("  trim ".parse(), Ok(Command::Trim));
+            assert_eq!("append 3".parse(), Ok(Command::Append(3)));
+            assert_eq!("append   0".parse(), Ok(Command::Append(0)));
+            assert_eq!("lowercase".parse(), Ok(Command::Lowercase));
+            assert_eq!("reverse".parse(), Ok(Command::Reverse));
+            assert_eq!("truncate 4".parse(), Ok(Command::Truncate(4)));
+            assert_eq!("repeat 2".parse(), Ok(Command::Repeat(2)));
+            assert_eq!(
+                "replace foo bar".parse(),
+                Ok(Command::Replace {
+                    from: String::from("foo"),
+                    to: String::from("bar"),
+                }),
+            );
+        }
+
+        #[test]
+        fn pipeline_into_sequence() {
+            let pipeline: Pipeline = "reverse | truncate 2".parse().unwrap();
+            let command = Command::from(pipeline);
+            assert_eq!(
+                command,
+                Command::Sequence(vec![Command::Reverse, Command::Truncate(2)]),
+            );
+            assert_eq!(command.apply(String::from("hello")), "ol");
+        }
+
+        #[test]
+        fn parse_pipeline() {
+            let pipeline: Pipeline = "trim | uppercase | append 3".parse().unwrap();
+            assert_eq!(
+                pipeline.commands,
+                [Command::Trim, Command::Uppercase, Command::Append(3)],
+            );
+
+            let pipeline: Pipeline = "append 1|trim|append 2".parse().unwrap();
+            assert_eq!(
+                pipeline.commands,
+                [Command::Append(1), Command::Trim, Command::Append(2)],
+            );
+        }
+
+        #[test]
+        fn apply_pipeline() {
+            let pipeline: Pipeline = "trim | uppercase | append 3".parse().unwrap();
+            assert_eq!(pipeline.apply(String::from("  foo ")), "FOObarbarbar");
+
+            let pipeline: Pipeline = "append 1 | uppercase".parse().unwrap();
+            assert_eq!(
+                pipeline.apply_all(vec![String::from("foo"), String::from(" ")]),
+                ["FOOBAR", " BAR"],
+            );
+        }
+
+        #[test]
+        fn text_fixtures() {
+            // Each line is "<input> => <pipeline> => <expected output>".
+            let fixtures = "hello => uppercase => HELLO
+ all roads lead to rome!  => trim => all roads lead to rome!
+foo => append 1 => foobar
+bar => append 5 => barbarbarbarbarbar
+ x  => append 1 | trim | uppercase => X BAR";
+
+            for fixture in fixtures.lines() {
+                let mut parts = fixture.split(" => ");
+                let input = parts.next().unwrap();
+                let pipeline: Pipeline = parts.next().unwrap().parse().unwrap();
+                let expected = parts.next().unwrap();
+                assert_eq!(pipeline.apply(input.to_string()), expected);
+            }
+        }
+
+        #[test]
+        fn empty_command() {
+            for (input, column) in [
+                ("", 1),
+                ("   ", 4),
+                ("| trim", 1),
+                ("trim |", 7),
+                ("trim || trim", 7),
+            ] {
+                assert_eq!(
+                    parse_error(input),
+                    ParseError {
+                        column,
+                        kind: ParseErrorKind::EmptyCommand,
+                    },
+                );
+            }
+        }
+
+        #[test]
+        fn unknown_command() {
+            assert_eq!(
+                parse_error("trim | capitalize"),
+                ParseError {
+                    column: 8,
+                    kind: ParseErrorKind::UnknownCommand(String::from("capitalize")),
+                },
+            );
+            // Commands are case-sensitive.
+            assert_eq!(parse_error("Trim").column, 1);
+        }
+
+        #[test]
+        fn missing_argument() {
+            assert_eq!(
+                parse_error("append | trim"),
+                ParseError {
+                    column: 8,
+                    kind: ParseErrorKind::MissingArgument,
+                },
+            );
+            assert_eq!(parse_error("trim | append").column, 14);
+            assert_eq!(parse_error("replace a").column, 10);
+            assert_eq!(parse_error("replace").column, 8);
+        }
+
+        #[test]
+        fn invalid_argument() {
+            let err = parse_error("uppercase | append -1");
+            assert_eq!(err.column, 20);
+            assert!(matches!(err.kind, ParseErrorKind::InvalidArgument(_)));
+            assert_eq!(
+                err.to_string(),
+                "column 20: invalid argument: invalid digit found in string",
+            );
+        }
+
+        #[test]
+        fn count_too_large() {
+            assert_eq!("repeat 65535".parse(), Ok(Command::Repeat(65535)));
+            assert_eq!(
+                "truncate 18446744073709551615".parse(),
+                Ok(Command::Truncate(usize::MAX))
+            );
+
+            let err = parse_error("append 18446744073709551615");
+            assert_eq!(err.column, 8);
+            assert_eq!(
+                err.to_string(),
+                "column 8: invalid argument: number too large to fit in target type",
+            );
+            let err = parse_error("trim | repeat 65536");
+            assert_eq!(err.column, 15);
+            assert!(matches!(err.kind, ParseErrorKind::InvalidArgument(_)));
+        }
+
+        #[test]
+        fn unexpected_argument() {
+            assert_eq!(
+                parse_error("trim now"),
+                ParseError {
+                    column: 6,
+                    kind: ParseErrorKind::UnexpectedArgument,
+                },
+            );
+            assert_eq!(parse_error("append 1 2").column, 10);
+            assert_eq!(parse_error("replace a b c").column, 13);
+        }
+
+        #[test]
+        fn columns_count_characters() {
+            // "ü" is 2 bytes long but only 1 column wide.
+            assert_eq!(parse_error("ü").column, 1);
+            assert_eq!(parse_error("trim | append ü").column, 15);
+            // The ideographic space is 3 bytes long.
+            assert_eq!(parse_error("trim\u{3000}| foo").column, 8);
+        }
+
+        #[test]
+        fn single_command_rejects_pipe() {
+            assert_eq!(
+                "trim | uppercase".parse::<Command>(),
+                Err(ParseError {
+                    column: 6,
+                    kind: ParseErrorKind::UnexpectedPipe,
+                }),
+            );
+        }
+    }
 }
@@ -7,6 +7,8 @@
 // Make the necessary code changes in the struct `ReportCard` and the impl
 // block to support alphabetical report cards in addition to numerical ones.
 
+use grades::{FromPercentage, Percentage};
+use std::collections::BTreeMap;
 use std::fmt::Display;
 
 // Make the struct generic over `T`.
@@ -29,12 +31,517 @@ impl<T: Display> ReportCard<T> {
     }
 }
 
+impl<T> ReportCard<T> {
+    // Creates a report card if the grade can be converted into the grade
+    // system `T`, e.g. `ReportCard::<NumericGrade>::new(2.1, …)`. Out-of-range
+    // grades are rejected.
+    fn new<G: TryInto<T>>(
+        grade: G,
+        student_name: String,
+        student_age: u8,
+    ) -> Result<Self, G::Error> {
+        Ok(Self {
+            grade: grade.try_into()?,
+            student_name,
+            student_age,
+        })
+    }
+
+    // The same report card in another grade system.
+    fn convert<U: From<T>>(&self) -> ReportCard<U>
+    where
+        T: Copy,
+    {
+        ReportCard {
+            grade: U::from(self.grade),
+            student_name: self.student_name.clone(),
+            student_age: self.student_age,
+        }
+    }
+
+    // The same report card in another grade system that might not represent
+    // the grade exactly, e.g. a percentage as a letter grade.
+    fn convert_rounded<U: FromPercentage>(&self) -> ReportCard<U>
+    where
+        T: Copy + Into<Percentage>,
+    {
+        ReportCard {
+            grade: U::from_percentage(self.grade.into()),
+            student_name: self.student_name.clone(),
+            student_age: self.student_age,
+        }
+    }
+}
+
+// The report cards of a class. All grades are in the same grade system `T`
+// and are compared as percentages.
+struct Class<T> {
+    report_cards: Vec<ReportCard<T>>,
+}
+
+impl<T> FromIterator<ReportCard<T>> for Class<T> {
+    fn from_iter<I: IntoIterator<Item = ReportCard<T>>>(iter: I) -> Self {
+        Self {
+            report_cards: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl<T> Class<T>
+where
+    T: Copy + Display + Into<Percentage> + FromPercentage,
+{
+    fn new() -> Self {
+        Self {
+            report_cards: Vec::new(),
+        }
+    }
+
+    fn add(&mut self, report_card: ReportCard<T>) {
+        self.report_cards.push(report_card);
+    }
+
+    // The same class in another grade system.
+    fn convert<U: From<T>>(&self) -> Class<U> {
+        self.report_cards.iter().map(ReportCard::convert).collect()
+    }
+
+    fn convert_rounded<U: FromPercentage>(&self) -> Class<U> {
+        self.report_cards
+            .iter()
+            .map(ReportCard::convert_rounded)
+            .collect()
+    }
+
+    fn percentages(&self) -> Vec<f64> {
+        self.report_cards
+            .iter()
+            .map(|report_card| report_card.grade.into().value())
+            .collect()
+    }
+
+    // The mean and the median are computed as percentages and then converted
+    // back into the grade system of the class.
+    fn from_percentage(value: f64) -> T {
+        // The mean of valid percentages is also valid, but rounding errors
+        // could push it slightly out of the range.
+        Percentage::try_from(value.clamp(0.0, 100.0))
+            .map(T::from_percentage)
+            .unwrap()
+    }
+
+    fn mean(&self) -> Option<T> {
+        if self.report_cards.is_empty() {
+            return None;
+        }
+
+        let percentages = self.percentages();
+        let mean = percentages.iter().sum::<f64>() / percentages.len() as f64;
+        Some(Self::from_percentage(mean))
+    }
+
+    fn median(&self) -> Option<T> {
+        let mut percentages = self.percentages();
+        percentages.sort_by(f64::total_cmp);
+
+        let middle = percentages.len() / 2;
+        let median = if percentages.len().is_multiple_of(2) {
+            (percentages.get(middle.checked_sub(1)?)? + percentages[middle]) / 2.0
+        } else {
+            percentages[middle]
+        };
+        Some(Self::from_percentage(median))
+    }
+
+    // The report cards from the best to the worst grade together with the rank
+    // of the student. Students with the same grade share a rank and are sorted
+    // by name, e.g. 1, 2, 2, 4.
+    fn ranking(&self) -> Vec<(usize, &ReportCard<T>)> {
+        let mut report_cards: Vec<(f64, &ReportCard<T>)> = self
+            .report_cards
+            .iter()
+            .map(|report_card| (report_card.grade.into().value(), report_card))
+            .collect();
+        report_cards.sort_by(|(a, report_card_a), (b, report_card_b)| {
+            b.total_cmp(a)
+                .then_with(|| report_card_a.student_name.cmp(&report_card_b.student_name))
+        });
+
+        let mut ranking: Vec<(usize, &ReportCard<T>)> = Vec::with_capacity(report_cards.len());
+        for (ind, (percentage, report_card)) in report_cards.iter().enumerate() {
+            let rank = match ind.checked_sub(1) {
+                Some(previous) if report_cards[previous].0 == *percentage => ranking[previous].0,
+                _ => ind + 1,
+            };
+            ranking.push((rank, report_card));
+        }
+
+        ranking
+    }
+
+    // The `n` best students. Ties at the end of the list are cut by name.
+    fn top(&self, n: usize) -> Vec<&ReportCard<T>> {
+        self.ranking()
+            .into_iter()
+            .take(n)
+            .map(|(_, report_card)| report_card)
+            .collect()
+    }
+
+    fn group_by_age(&self) -> BTreeMap<u8, Vec<&ReportCard<T>>> {
+        let mut groups = BTreeMap::<u8, Vec<&ReportCard<T>>>::new();
+        for report_card in &self.report_cards {
+            groups
+                .entry(report_card.student_age)
+                .or_default()
+                .push(report_card);
+        }
+        groups
+    }
+
+    // One line per student printed with `ReportCard::print` in ranking order.
+    fn print(&self) -> String {
+        self.ranking()
+            .into_iter()
+            .map(|(rank, report_card)| format!("{rank}. {}\n", report_card.print()))
+            .collect()
+    }
+
+    // The ranking as a plain-text table with aligned columns.
+    fn render_table(&self) -> String {
+        const HEADER: [&str; 4] = ["Rank", "Student", "Age", "Grade"];
+
+        let rows: Vec<[String; 4]> = self
+            .ranking()
+            .into_iter()
+            .map(|(rank, report_card)| {
+                [
+                    rank.to_string(),
+                    report_card.student_name.clone(),
+                    report_card.student_age.to_string(),
+                    report_card.grade.to_string(),
+                ]
+            })
+            .collect();
+
+        let mut widths = HEADER.map(|title| title.chars().count());
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+
+        let mut table = String::new();
+        let mut push_row = |row: [&str; 4]| {
+            // Numbers are aligned to the right, text to the left.
+            let line = format!(
+                "{:>rank$}  {:<name$}  {:>age$}  {:<grade$}",
+                row[0],
+                row[1],
+                row[2],
+                row[3],
+                rank = widths[0],
+                name = widths[1],
+                age = widths[2],
+                grade = widths[3],
+            );
+            table.push_str(line.trim_end());
+            table.push('\n');
+        };
+
+        push_row(HEADER);
+        for row in &rows {
+            push_row(row.each_ref().map(String::as_str));
+        }
+
+        table
+    }
+}
+
+// Typed grade systems. Their fields are private, so a grade can only be created
+// through `TryFrom` which checks its range.
+//
+// Percentages and numeric grades are converted into each other linearly with
+// `From` without losing information. Letter grades cover a range of
+// percentages, so converting from one with `From` uses the middle of its range.
+// Converting to a letter grade is lossy and has to be done explicitly with
+// `to_letter_grade` or `FromPercentage`.
+mod grades {
+    use std::error::Error;
+    use std::fmt;
+    use std::str::FromStr;
+
+    #[derive(Debug, PartialEq)]
+    pub enum GradeError {
+        OutOfRange,
+        InvalidLetterGrade(String),
+    }
+
+    impl fmt::Display for GradeError {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            match self {
+                GradeError::OutOfRange => f.write_str("grade is out of range"),
+                GradeError::InvalidLetterGrade(grade) => {
+                    write!(f, "`{grade}` isn't a letter grade")
+                }
+            }
+        }
+    }
+
+    impl Error for GradeError {}
+
+    // A numeric grade from 1.0 (best) to 5.0 (worst).
+    #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+    pub struct NumericGrade(f64);
+
+    impl NumericGrade {
+        pub const BEST: f64 = 1.0;
+        pub const WORST: f64 = 5.0;
+
+        pub fn value(self) -> f64 {
+            self.0
+        }
+    }
+
+    impl TryFrom<f64> for NumericGrade {
+        type Error = GradeError;
+
+        fn try_from(value: f64) -> Result<Self, Self::Error> {
+            // `contains` is also `false` for NaN.
+            if (Self::BEST..=Self::WORST).contains(&value) {
+                Ok(Self(value))
+            } else {
+                Err(GradeError::OutOfRange)
+            }
+        }
+    }
+
+    impl fmt::Display for NumericGrade {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "{:.1}", self.0)
+        }
+    }
+
+    // A percentage from 0 (worst) to 100 (best).
+    #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+    pub struct Percentage(f64);
+
+    impl Percentage {
+        pub fn value(self) -> f64 {
+            self.0
+        }
+    }
+
+    impl TryFrom<f64> for Percentage {
+        type Error = GradeError;
+
+        fn try_from(value: f64) -> Result<Self, Self::Error> {
+            if (0.0..=100.0).contains(&value) {
+                Ok(Self(value))
+            } else {
+                Err(GradeError::OutOfRange)
+            }
+        }
+    }
+
+    impl fmt::Display for Percentage {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "{:.1}%", self.0)
+        }
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    pub enum Letter {
+        A,
+        B,
+        C,
+        D,
+        F,
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    pub enum Modifier {
+        Plus,
+        Plain,
+        Minus,
+    }
+
+    // A letter grade from A+ (best) to F- (worst).
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    pub struct LetterGrade {
+        pub letter: Letter,
+        pub modifier: Modifier,
+    }
+
+    impl LetterGrade {
+        pub const fn new(letter: Letter, modifier: Modifier) -> Self {
+            Self { letter, modifier }
+        }
+    }
+
+    // Every letter grade from best to worst with the lowest percentage that
+    // still gets that grade.
+    const LETTER_GRADES: [(LetterGrade, f64); 15] = {
+        use Letter::*;
+        use Modifier::*;
+        [
+            (LetterGrade::new(A, Plus), 97.0),
+            (LetterGrade::new(A, Plain), 93.0),
+            (LetterGrade::new(A, Minus), 90.0),
+            (LetterGrade::new(B, Plus), 87.0),
+            (LetterGrade::new(B, Plain), 83.0),
+            (LetterGrade::new(B, Minus), 80.0),
+            (LetterGrade::new(C, Plus), 77.0),
+            (LetterGrade::new(C, Plain), 73.0),
+            (LetterGrade::new(C, Minus), 70.0),
+            (LetterGrade::new(D, Plus), 67.0),
+            (LetterGrade::new(D, Plain), 63.0),
+            (LetterGrade::new(D, Minus), 60.0),
+            (LetterGrade::new(F, Plus), 40.0),
+            (LetterGrade::new(F, Plain), 20.0),
+            (LetterGrade::new(F, Minus), 0.0),
+        ]
+    };
+
+    impl fmt::Display for LetterGrade {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            let letter = match self.letter {
+                Letter::A => 'A',
+                Letter::B => 'B',
+                Letter::C => 'C',
+                Letter::D => 'D',
+                Letter::F => 'F',
+            };
+            let modifier = match self.modifier {
+                Modifier::Plus => "+",
+                Modifier::Plain => "",
+                Modifier::Minus => "-",
+            };
+            write!(f, "{letter}{modifier}")
+        }
+    }
+
+    impl FromStr for LetterGrade {
+        type Err = GradeError;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let invalid = || GradeError::InvalidLetterGrade(s.to_string());
+
+            let mut chars = s.chars();
+            let letter = match chars.next() {
+                Some('A') => Letter::A,
+                Some('B') => Letter::B,
+                Some('C') => Letter::C,
+                Some('D') => Letter::D,
+                Some('F') => Letter::F,
+                _ => return Err(invalid()),
+            };
+            let modifier = match chars.next() {
+                Some('+') => Modifier::Plus,
+                None => Modifier::Plain,
+                Some('-') => Modifier::Minus,
+                Some(_) => return Err(invalid()),
+            };
+            if chars.next().is_some() {
+                return Err(invalid());
+            }
+
+            Ok(Self { letter, modifier })
+        }
+    }
+
+    impl TryFrom<&str> for LetterGrade {
+        type Error = GradeError;
+
+        fn try_from(value: &str) -> Result<Self, Self::Error> {
+            value.parse()
+        }
+    }
+
+    impl From<NumericGrade> for Percentage {
+        fn from(grade: NumericGrade) -> Self {
+            let range = NumericGrade::WORST - NumericGrade::BEST;
+            Self((NumericGrade::WORST - grade.0) / range * 100.0)
+        }
+    }
+
+    impl From<Percentage> for NumericGrade {
+        fn from(percentage: Percentage) -> Self {
+            let range = NumericGrade::WORST - NumericGrade::BEST;
+            Self(NumericGrade::WORST - percentage.0 / 100.0 * range)
+        }
+    }
+
+    impl Percentage {
+        // The letter grade whose range contains this percentage.
+        pub fn to_letter_grade(self) -> LetterGrade {
+            LETTER_GRADES
+                .iter()
+                .find(|(_, lowest)| self.0 >= *lowest)
+                .map_or(
+                    LetterGrade::new(Letter::F, Modifier::Minus),
+                    |(grade, _)| *grade,
+                )
+        }
+    }
+
+    impl From<LetterGrade> for Percentage {
+        fn from(grade: LetterGrade) -> Self {
+            // Every letter grade is in the table.
+            let ind = LETTER_GRADES
+                .iter()
+                .position(|(letter_grade, _)| *letter_grade == grade)
+                .unwrap();
+            let lowest = LETTER_GRADES[ind].1;
+            let highest = ind.checked_sub(1).map_or(100.0, |ind| LETTER_GRADES[ind].1);
+            Self((lowest + highest) / 2.0)
+        }
+    }
+
+    impl NumericGrade {
+        pub fn to_letter_grade(self) -> LetterGrade {
+            Percentage::from(self).to_letter_grade()
+        }
+    }
+
+    impl From<LetterGrade> for NumericGrade {
+        fn from(grade: LetterGrade) -> Self {
+            Percentage::from(grade).into()
+        }
+    }
+
+    // Converts a percentage into a grade system, rounding to the closest
+    // grade if the system can't represent the percentage exactly.
+    pub trait FromPercentage {
+        fn from_percentage(percentage: Percentage) -> Self;
+    }
+
+    impl FromPercentage for Percentage {
+        fn from_percentage(percentage: Percentage) -> Self {
+            percentage
+        }
+    }
+
+    impl FromPercentage for NumericGrade {
+        fn from_percentage(percentage: Percentage) -> Self {
+            percentage.into()
+        }
+    }
+
+    impl FromPercentage for LetterGrade {
+        fn from_percentage(percentage: Percentage) -> Self {
+            percentage.to_letter_grade()
+        }
+    }
+}
+
 fn main() {
     // You can optionally experiment here.
 }
 
 #[cfg(test)]
 mod tests {
+    use super::grades::{GradeError, Letter, LetterGrade, Modifier, NumericGrade, Percentage};
     use super::*;
 
     #[test]
@@ -62,4 +569,287 @@ mod tests {
             "Gary Plotter (11) - achieved a grade of A+",
         );
     }
+
+    #[test]
+    fn validate_grades() {
+        assert!(NumericGrade::try_from(1.0).is_ok());
+        assert!(NumericGrade::try_from(5.0).is_ok());
+        for value in [0.9, 5.5, -1.0, f64::NAN, f64::INFINITY] {
+            assert_eq!(NumericGrade::try_from(value), Err(GradeError::OutOfRange));
+        }
+
+        assert!(Percentage::try_from(0.0).is_ok());
+        assert!(Percentage::try_from(100.0).is_ok());
+        for value in [-0.1, 100.1, f64::NAN] {
+            assert_eq!(Percentage::try_from(value), Err(GradeError::OutOfRange));
+        }
+
+        assert_eq!(
+            "A+".parse(),
+            Ok(LetterGrade::new(Letter::A, Modifier::Plus)),
+        );
+        assert_eq!(
+            "C".parse(),
+            Ok(LetterGrade::new(Letter::C, Modifier::Plain))
+        );
+        assert_eq!(
+            "F-".parse(),
+            Ok(LetterGrade::new(Letter::F, Modifier::Minus)),
+        );
+        for grade in ["", "E", "a", "A++", "B*", " B"] {
+            assert_eq!(
+                grade.parse::<LetterGrade>(),
+                Err(GradeError::InvalidLetterGrade(grade.to_string())),
+            );
+        }
+    }
+
+    #[test]
+    fn report_card_rejects_out_of_range_grades() {
+        let report_card =
+            ReportCard::<NumericGrade>::new(2.1, "Tom Wriggle".to_string(), 12).unwrap();
+        assert_eq!(
+            report_card.print(),
+            "Tom Wriggle (12) - achieved a grade of 2.1",
+        );
+
+        assert_eq!(
+            ReportCard::<NumericGrade>::new(5.5, "Tom Wriggle".to_string(), 12).err(),
+            Some(GradeError::OutOfRange),
+        );
+        assert_eq!(
+            ReportCard::<Percentage>::new(101.0, "Tom Wriggle".to_string(), 12).err(),
+            Some(GradeError::OutOfRange),
+        );
+        assert_eq!(
+            ReportCard::<LetterGrade>::new("G", "Gary Plotter".to_string(), 11).err(),
+            Some(GradeError::InvalidLetterGrade("G".to_string())),
+        );
+
+        // A typed grade is already valid.
+        let grade = LetterGrade::new(Letter::B, Modifier::Minus);
+        let report_card =
+            ReportCard::<LetterGrade>::new(grade, "Gary Plotter".to_string(), 11).unwrap();
+        assert_eq!(
+            report_card.print(),
+            "Gary Plotter (11) - achieved a grade of B-",
+        );
+    }
+
+    #[test]
+    fn numeric_percentage_conversions() {
+        let percentage = |value| Percentage::try_from(value).unwrap();
+        let numeric = |value| NumericGrade::try_from(value).unwrap();
+
+        assert_eq!(NumericGrade::from(percentage(100.0)), numeric(1.0));
+        assert_eq!(NumericGrade::from(percentage(0.0)), numeric(5.0));
+        assert_eq!(NumericGrade::from(percentage(50.0)), numeric(3.0));
+        assert_eq!(Percentage::from(numeric(2.0)), percentage(75.0));
+
+        // Converting back and forth doesn't lose information.
+        for value in [1.0, 1.3, 2.1, 3.7, 4.99, 5.0] {
+            let back = NumericGrade::from(Percentage::from(numeric(value)));
+            assert!((back.value() - value).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn letter_grade_conversions() {
+        let percentage = |value| Percentage::try_from(value).unwrap();
+        let letter = |grade: &str| grade.parse::<LetterGrade>().unwrap();
+
+        assert_eq!(percentage(100.0).to_letter_grade(), letter("A+"));
+        assert_eq!(percentage(97.0).to_letter_grade(), letter("A+"));
+        assert_eq!(percentage(96.9).to_letter_grade(), letter("A"));
+        assert_eq!(percentage(85.0).to_letter_grade(), letter("B"));
+        assert_eq!(percentage(60.0).to_letter_grade(), letter("D-"));
+        assert_eq!(percentage(59.0).to_letter_grade(), letter("F+"));
+        assert_eq!(percentage(0.0).to_letter_grade(), letter("F-"));
+
+        assert_eq!(Percentage::from(letter("A+")), percentage(98.5));
+        assert_eq!(Percentage::from(letter("B")), percentage(85.0));
+        assert_eq!(Percentage::from(letter("F-")), percentage(10.0));
+
+        // Converting to a percentage and back keeps the letter grade.
+        for letter_grade in [
+            "A+", "A", "A-", "B+", "B", "B-", "C+", "C", "C-", "D+", "D", "D-", "F+", "F", "F-",
+        ] {
+            let grade = letter(letter_grade);
+            assert_eq!(Percentage::from(grade).to_letter_grade(), grade);
+            assert_eq!(NumericGrade::from(grade).to_letter_grade(), grade);
+            assert_eq!(grade.to_string(), letter_grade);
+        }
+
+        // But converting from a percentage to a letter grade is lossy.
+        assert_eq!(
+            Percentage::from(percentage(91.0).to_letter_grade()),
+            percentage(91.5),
+        );
+    }
+
+    #[test]
+    fn print_in_different_grade_systems() {
+        let report_card =
+            ReportCard::<Percentage>::new(85.0, "Gary Plotter".to_string(), 11).unwrap();
+        assert_eq!(
+            report_card.print(),
+            "Gary Plotter (11) - achieved a grade of 85.0%",
+        );
+        assert_eq!(
+            report_card.convert::<NumericGrade>().print(),
+            "Gary Plotter (11) - achieved a grade of 1.6",
+        );
+        assert_eq!(
+            report_card.convert_rounded::<LetterGrade>().print(),
+            "Gary Plotter (11) - achieved a grade of B",
+        );
+        assert_eq!(
+            report_card
+                .convert_rounded::<LetterGrade>()
+                .convert::<NumericGrade>()
+                .print(),
+            "Gary Plotter (11) - achieved a grade of 1.6",
+        );
+    }
+
+    fn report_card<T, G: TryInto<T>>(grade: G, student_name: &str, student_age: u8) -> ReportCard<T>
+    where
+        G::Error: std::fmt::Debug,
+    {
+        ReportCard::new(grade, student_name.to_string(), student_age).unwrap()
+    }
+
+    fn class() -> Class<Percentage> {
+        [
+            report_card(72.0, "Tom Wriggle", 12),
+            report_card(91.0, "Gary Plotter", 11),
+            report_card(85.0, "Ron Measley", 11),
+            report_card(98.0, "Hermione Franger", 12),
+            report_card(85.0, "Luna Loveboot", 10),
+        ]
+        .into_iter()
+        .collect()
+    }
+
+    fn names<T>(report_cards: &[&ReportCard<T>]) -> Vec<String> {
+        report_cards
+            .iter()
+            .map(|report_card| report_card.student_name.clone())
+            .collect()
+    }
+
+    #[test]
+    fn class_ranking() {
+        let class = class();
+        let ranking = class.ranking();
+        let ranks: Vec<(usize, &str)> = ranking
+            .iter()
+            .map(|(rank, report_card)| (*rank, report_card.student_name.as_str()))
+            .collect();
+        assert_eq!(
+            ranks,
+            [
+                (1, "Hermione Franger"),
+                (2, "Gary Plotter"),
+                (3, "Luna Loveboot"),
+                (3, "Ron Measley"),
+                (5, "Tom Wriggle"),
+            ],
+        );
+
+        assert_eq!(names(&class.top(2)), ["Hermione Franger", "Gary Plotter"]);
+        assert_eq!(class.top(10).len(), 5);
+        assert!(class.top(0).is_empty());
+    }
+
+    #[test]
+    fn class_mean_and_median() {
+        let class = class();
+        assert_eq!(class.mean(), Some(Percentage::try_from(86.2).unwrap()));
+        assert_eq!(class.median(), Some(Percentage::try_from(85.0).unwrap()));
+
+        // The same class in other grade systems.
+        assert_eq!(
+            class
+                .convert_rounded::<LetterGrade>()
+                .median()
+                .unwrap()
+                .to_string(),
+            "B"
+        );
+        assert_eq!(
+            class.convert::<NumericGrade>().mean().unwrap().to_string(),
+            "1.6"
+        );
+
+        let mut class: Class<NumericGrade> = Class::new();
+        assert!(class.mean().is_none());
+        assert!(class.median().is_none());
+        class.add(report_card(2.0, "Tom Wriggle", 12));
+        assert_eq!(class.median().unwrap().to_string(), "2.0");
+        class.add(report_card(3.0, "Gary Plotter", 11));
+        assert_eq!(class.median().unwrap().to_string(), "2.5");
+        assert_eq!(class.mean().unwrap().to_string(), "2.5");
+    }
+
+    #[test]
+    fn class_letter_grades() {
+        let class: Class<LetterGrade> = [
+            report_card("B+", "Tom Wriggle", 12),
+            report_card("A", "Gary Plotter", 11),
+            report_card("F", "Ron Measley", 11),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(names(&class.top(1)), ["Gary Plotter"]);
+        // (95 + 88.5 + 30) / 3 = 71.16…
+        assert_eq!(class.mean().unwrap().to_string(), "C-");
+        assert_eq!(class.median().unwrap().to_string(), "B+");
+    }
+
+    #[test]
+    fn class_group_by_age() {
+        let class = class();
+        let groups = class.group_by_age();
+        assert_eq!(groups.keys().copied().collect::<Vec<_>>(), [10, 11, 12]);
+        assert_eq!(names(&groups[&10]), ["Luna Loveboot"]);
+        assert_eq!(names(&groups[&11]), ["Gary Plotter", "Ron Measley"]);
+        assert_eq!(names(&groups[&12]), ["Tom Wriggle", "Hermione Franger"]);
+    }
+
+    #[test]
+    fn print_class() {
+        let class = class().convert_rounded::<LetterGrade>();
+        assert_eq!(
+            class.print(),
+            "1. Hermione Franger (12) - achieved a grade of A+
+2. Gary Plotter (11) - achieved a grade of A-
+3. Luna Loveboot (10) - achieved a grade of B
+3. Ron Measley (11) - achieved a grade of B
+5. Tom Wriggle (12) - achieved a grade of C-
+",
+        );
+    }
+
+    #[test]
+    fn render_class_table() {
+        assert_eq!(
+            class().render_table(),
+            "Rank  Student           Age  Grade
+   1  Hermione Franger   12  98.0%
+   2  Gary Plotter       11  91.0%
+   3  Luna Loveboot      10  85.0%
+   3  Ron Measley        11  85.0%
+   5  Tom Wriggle        12  72.0%
+",
+        );
+
+        let class: Class<LetterGrade> = [report_card("A+", "Al", 9)].into_iter().collect();
+        assert_eq!(
+            class.render_table(),
+            "Rank  Student  Age  Grade
+   1  Al         9  A+
+",
+        );
+    }
 }
@@ -0,0 +1,101 @@
+// Runs a bin with `cargo run` or, if it has tests, with `cargo test`.
+
+use std::env;
+use std::ffi::OsString;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::process::Command;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mode {
+    Run,
+    Test,
+}
+
+impl Mode {
+    // Bins with a `#[cfg(test)]` module are tested, the others are run.
+    pub fn for_source(source: &str) -> Self {
+        if source
+            .lines()
+            .any(|line| line.trim_start().starts_with("#[cfg(test)]"))
+        {
+            Self::Test
+        } else {
+            Self::Run
+        }
+    }
+
+    pub fn cargo_args(self, bin: &str) -> [&str; 4] {
+        let command = match self {
+            Self::Run => "run",
+            Self::Test => "test",
+        };
+        [command, "--quiet", "--bin", bin]
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Outcome {
+    pub mode: Mode,
+    pub success: bool,
+    // stdout followed by stderr.
+    pub output: String,
+}
+
+// The Cargo that runs the current tool or the one in `PATH`.
+fn cargo() -> OsString {
+    env::var_os("CARGO").unwrap_or_else(|| OsString::from("cargo"))
+}
+
+// `path` is relative to `root`.
+pub fn check_bin(root: &Path, bin: &str, path: &Path) -> io::Result<Outcome> {
+    let mode = Mode::for_source(&fs::read_to_string(root.join(path))?);
+    let output = Command::new(cargo())
+        .args(mode.cargo_args(bin))
+        .current_dir(root)
+        .output()?;
+
+    let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+    text.push_str(&String::from_utf8_lossy(&output.stderr));
+    Ok(Outcome {
+        mode,
+        success: output.status.success(),
+        output: text,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mode_for_source() {
+        assert_eq!(Mode::for_source("fn main() {}\n"), Mode::Run);
+        assert_eq!(
+            Mode::for_source("fn main() {}\n\n#[cfg(test)]\nmod tests {}\n"),
+            Mode::Test,
+        );
+        assert_eq!(
+            Mode::for_source("mod inner {\n    #[cfg(test)]\n    mod tests {}\n}\n"),
+            Mode::Test,
+        );
+        // Mentioning the attribute in a comment doesn't count.
+        assert_eq!(
+            Mode::for_source("// Add a #[cfg(test)] module\n"),
+            Mode::Run
+        );
+    }
+
+    #[test]
+    fn cargo_args() {
+        assert_eq!(
+            Mode::Run.cargo_args("intro1"),
+            ["run", "--quiet", "--bin", "intro1"],
+        );
+        assert_eq!(
+            Mode::Test.cargo_args("if1"),
+            ["test", "--quiet", "--bin", "if1"],
+        );
+    }
+}
@@ -0,0 +1,268 @@
+// An index of the chapters built from `exercises/README.md`, which maps the
+// chapters to sections of the Rust Book, the "Further information" links in
+// the README of each chapter and the exercises in `Cargo.toml`.
+
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::manifest::{self, Exercise, EXERCISES_DIR};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Link {
+    pub title: String,
+    pub url: String,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Chapter {
+    // The directory of the chapter, e.g. `18_iterators`.
+    pub name: String,
+    // E.g. `§13.2-4`. Empty if the chapter isn't covered by the book.
+    pub book_sections: Vec<String>,
+    pub links: Vec<Link>,
+    // In the order of `Cargo.toml`.
+    pub exercises: Vec<Exercise>,
+}
+
+impl Chapter {
+    pub fn topic(&self) -> &str {
+        manifest::chapter_topic(&self.name)
+    }
+}
+
+// The rows of the table in `exercises/README.md` as pairs of a topic and its
+// book sections, e.g. `("primitive_types", ["§3.2", "§4.3"])`.
+pub fn parse_book_sections(readme: &str) -> Vec<(String, Vec<String>)> {
+    readme
+        .lines()
+        .filter_map(|line| {
+            let mut cells = line.trim().strip_prefix('|')?.split('|').map(str::trim);
+            let topic = cells.next()?;
+            let sections = cells.next()?;
+            // Skip the header and the separator row.
+            if topic == "Exercise" || topic.starts_with('-') {
+                return None;
+            }
+
+            let sections = sections
+                .split(',')
+                .map(str::trim)
+                .filter(|section| !section.is_empty() && *section != "n/a")
+                .map(String::from)
+                .collect();
+            Some((topic.to_string(), sections))
+        })
+        .collect()
+}
+
+// Parses `[title](url)` and removes Markdown escapes from the title.
+fn parse_link(text: &str) -> Option<Link> {
+    let (title, rest) = text.strip_prefix('[')?.split_once("](")?;
+    let (url, _) = rest.split_once(')')?;
+
+    let mut unescaped = String::with_capacity(title.len());
+    let mut chars = title.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => unescaped.extend(chars.next()),
+            c => unescaped.push(c),
+        }
+    }
+
+    Some(Link {
+        title: unescaped,
+        url: url.to_string(),
+    })
+}
+
+// The list items with links in the "Further information" section of a
+// chapter's README.
+pub fn parse_links(readme: &str) -> Vec<Link> {
+    readme
+        .lines()
+        .skip_while(|line| {
+            !line
+                .strip_prefix("## ")
+                .is_some_and(|heading| heading.trim().eq_ignore_ascii_case("further information"))
+        })
+        .skip(1)
+        .take_while(|line| !line.starts_with('#'))
+        .filter_map(|line| line.trim().strip_prefix("- "))
+        .filter_map(parse_link)
+        .collect()
+}
+
+// Builds the index from the exercises in `Cargo.toml`, the table of
+// `exercises/README.md` and the README of each chapter. `chapter_readme`
+// returns `None` for a chapter without a README.
+pub fn index(
+    exercises: &[Exercise],
+    book_sections: &[(String, Vec<String>)],
+    mut chapter_readme: impl FnMut(&str) -> Option<String>,
+) -> Vec<Chapter> {
+    manifest::group_by_chapter(exercises)
+        .into_iter()
+        .map(|(name, exercises)| {
+            let topic = manifest::chapter_topic(name);
+            let book_sections = book_sections
+                .iter()
+                .find(|(row_topic, _)| row_topic == topic)
+                .map(|(_, sections)| sections.clone())
+                .unwrap_or_default();
+            let links = chapter_readme(name)
+                .map(|readme| parse_links(&readme))
+                .unwrap_or_default();
+
+            Chapter {
+                name: name.to_string(),
+                book_sections,
+                links,
+                exercises: exercises.into_iter().cloned().collect(),
+            }
+        })
+        .collect()
+}
+
+pub fn read_index(root: &Path) -> Result<Vec<Chapter>, Box<dyn Error>> {
+    let exercises = manifest::read_exercises(root)?;
+    let exercises_dir = root.join(EXERCISES_DIR);
+    let readme = fs::read_to_string(exercises_dir.join("README.md"))?;
+
+    let mut error = None;
+    let chapters = index(
+        &exercises,
+        &parse_book_sections(&readme),
+        |name| match fs::read_to_string(exercises_dir.join(name).join("README.md")) {
+            Ok(readme) => Some(readme),
+            Err(e) => {
+                if e.kind() != io::ErrorKind::NotFound {
+                    error.get_or_insert(e);
+                }
+                None
+            }
+        },
+    );
+
+    match error {
+        Some(e) => Err(e.into()),
+        None => Ok(chapters),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    const README: &str = "\
+# Exercise to Book Chapter mapping
+
+| Exercise               | Book Chapter        |
+| ---------------------- | ------------------- |
+| variables              | §3.1                |
+| primitive_types        | §3.2, §4.3          |
+| conversions            | n/a                 |
+";
+
+    const CHAPTER_README: &str = "\
+# Smart Pointers
+
+Some text.
+
+## Further Information
+
+For this section, the book links are especially important.
+
+- [Smart Pointers](https://doc.rust-lang.org/book/ch15-00-smart-pointers.html)
+- [Rc\\<T\\>, the Reference Counted Smart Pointer](https://doc.rust-lang.org/book/ch15-04-rc.html)
+- [GitHub Repository](https://github.com/rust-lang/rust-clippy).
+- Not a link
+
+## Another section
+
+- [Ignored](https://example.com)
+";
+
+    #[test]
+    fn book_sections() {
+        let sections = |sections: &[&str]| sections.iter().map(|s| s.to_string()).collect();
+        assert_eq!(
+            parse_book_sections(README),
+            [
+                (String::from("variables"), sections(&["§3.1"])),
+                (String::from("primitive_types"), sections(&["§3.2", "§4.3"])),
+                (String::from("conversions"), sections(&[])),
+            ],
+        );
+    }
+
+    #[test]
+    fn further_information_links() {
+        let link = |title: &str, url: &str| Link {
+            title: title.to_string(),
+            url: url.to_string(),
+        };
+        assert_eq!(
+            parse_links(CHAPTER_README),
+            [
+                link(
+                    "Smart Pointers",
+                    "https://doc.rust-lang.org/book/ch15-00-smart-pointers.html",
+                ),
+                link(
+                    "Rc<T>, the Reference Counted Smart Pointer",
+                    "https://doc.rust-lang.org/book/ch15-04-rc.html",
+                ),
+                link(
+                    "GitHub Repository",
+                    "https://github.com/rust-lang/rust-clippy"
+                ),
+            ],
+        );
+        assert_eq!(parse_links("# Quizzes\n\n- [Not further](x)\n"), []);
+    }
+
+    #[test]
+    fn index_chapters() {
+        let exercise = |name: &str, chapter: &str| Exercise {
+            name: name.to_string(),
+            chapter: chapter.to_string(),
+            path: PathBuf::from(format!("exercises/{chapter}/{name}.rs")),
+        };
+        let exercises = [
+            exercise("variables1", "01_variables"),
+            exercise("variables2", "01_variables"),
+            exercise("quiz1", "quizzes"),
+            exercise("as_ref_mut", "23_conversions"),
+        ];
+
+        let chapters = index(&exercises, &parse_book_sections(README), |name| {
+            (name == "01_variables").then(|| CHAPTER_README.to_string())
+        });
+
+        assert_eq!(chapters.len(), 3);
+        assert_eq!(chapters[0].name, "01_variables");
+        assert_eq!(chapters[0].topic(), "variables");
+        assert_eq!(chapters[0].book_sections, ["§3.1"]);
+        assert_eq!(chapters[0].links.len(), 3);
+        assert_eq!(chapters[0].exercises, exercises[..2]);
+        assert!(chapters[1].book_sections.is_empty());
+        assert!(chapters[1].links.is_empty());
+        assert!(chapters[2].book_sections.is_empty());
+    }
+
+    #[test]
+    fn read_own_index() {
+        let chapters = read_index(manifest::root()).unwrap();
+        let iterators = chapters.iter().find(|c| c.topic() == "iterators").unwrap();
+        assert_eq!(iterators.book_sections, ["§13.2-4"]);
+        assert_eq!(iterators.links[0].title, "Iterator");
+        assert_eq!(iterators.exercises[0].name, "iterators1");
+        // Every chapter except the quizzes has links.
+        assert!(chapters
+            .iter()
+            .all(|c| c.topic() == "quizzes" || !c.links.is_empty()));
+    }
+}
@@ -0,0 +1,306 @@
+// A line diff based on the longest common subsequence (LCS) of the lines and
+// its output in the unified format of `diff -u`.
+
+use std::fmt::Write as _;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Edit {
+    // Indexes into the old and new lines.
+    Equal(usize, usize),
+    Delete(usize),
+    Insert(usize),
+}
+
+// The edits turning `old` into `new`. Within a change, deletions come before
+// insertions. Takes O(n * m) time and memory, which is fine for exercises.
+pub fn diff<T: PartialEq>(old: &[T], new: &[T]) -> Vec<Edit> {
+    // `lcs[i][j]` is the length of the LCS of `old[i..]` and `new[j..]`.
+    let mut lcs = vec![vec![0_usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut edits = Vec::with_capacity(old.len().max(new.len()));
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            edits.push(Edit::Equal(i, j));
+            i += 1;
+            j += 1;
+        } else if j == new.len() || (i < old.len() && lcs[i + 1][j] >= lcs[i][j + 1]) {
+            edits.push(Edit::Delete(i));
+            i += 1;
+        } else {
+            edits.push(Edit::Insert(j));
+            j += 1;
+        }
+    }
+    edits
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Line<'a> {
+    // 1-based number in the file.
+    pub number: usize,
+    pub text: &'a str,
+}
+
+fn is_comment_only(line: &str) -> bool {
+    line.trim_start().starts_with("//")
+}
+
+// The lines of `text`, optionally without the lines that only contain a
+// comment. The remaining lines keep their numbers.
+pub fn lines(text: &str, ignore_comments: bool) -> Vec<Line<'_>> {
+    text.lines()
+        .enumerate()
+        .map(|(index, text)| Line {
+            number: index + 1,
+            text,
+        })
+        .filter(|line| !(ignore_comments && is_comment_only(line.text)))
+        .collect()
+}
+
+// The range of a hunk in one file for its header, e.g. `3,2` for 2 lines
+// starting at line 3 or `3` for only line 3. If lines were ignored, the count is
+// the number of shown lines.
+fn hunk_range(lines: &[Line], first: usize, count: usize) -> String {
+    let start = match count {
+        // An empty range starts at the line before it.
+        0 => first
+            .checked_sub(1)
+            .map_or(0, |before| lines[before].number),
+        _ => lines[first].number,
+    };
+    match count {
+        1 => start.to_string(),
+        _ => format!("{start},{count}"),
+    }
+}
+
+// Formats the differences between `old` and `new` like `diff -u` with
+// `context` unchanged lines around each change. Returns an empty string if
+// there are no differences.
+pub fn unified(
+    old_name: &str,
+    new_name: &str,
+    old: &[Line],
+    new: &[Line],
+    context: usize,
+) -> String {
+    let old_texts: Vec<&str> = old.iter().map(|line| line.text).collect();
+    let new_texts: Vec<&str> = new.iter().map(|line| line.text).collect();
+    let edits = diff(&old_texts, &new_texts);
+
+    let changes: Vec<usize> = edits
+        .iter()
+        .enumerate()
+        .filter(|(_, edit)| !matches!(edit, Edit::Equal(..)))
+        .map(|(index, _)| index)
+        .collect();
+    if changes.is_empty() {
+        return String::new();
+    }
+
+    // Ranges of `edits` with changes closer than `2 * context` merged.
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for &change in &changes {
+        let start = change.saturating_sub(context);
+        let end = change
+            .saturating_add(context)
+            .saturating_add(1)
+            .min(edits.len());
+        match hunks.last_mut() {
+            Some((_, last_end)) if start <= *last_end => *last_end = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    let mut output = format!("--- {old_name}\n+++ {new_name}\n");
+    for (start, end) in hunks {
+        let hunk = &edits[start..end];
+
+        // The first old and new index of the hunk, also for hunks that start
+        // with an insertion or deletion.
+        let (mut old_first, mut new_first) = (0, 0);
+        for edit in &edits[..start] {
+            match edit {
+                Edit::Equal(..) => {
+                    old_first += 1;
+                    new_first += 1;
+                }
+                Edit::Delete(_) => old_first += 1,
+                Edit::Insert(_) => new_first += 1,
+            }
+        }
+        let old_count = hunk
+            .iter()
+            .filter(|e| !matches!(e, Edit::Insert(_)))
+            .count();
+        let new_count = hunk
+            .iter()
+            .filter(|e| !matches!(e, Edit::Delete(_)))
+            .count();
+
+        let _ = writeln!(
+            output,
+            "@@ -{} +{} @@",
+            hunk_range(old, old_first, old_count),
+            hunk_range(new, new_first, new_count),
+        );
+        for edit in hunk {
+            let _ = match *edit {
+                Edit::Equal(i, _) => writeln!(output, " {}", old[i].text),
+                Edit::Delete(i) => writeln!(output, "-{}", old[i].text),
+                Edit::Insert(j) => writeln!(output, "+{}", new[j].text),
+            };
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edits() {
+        use Edit::*;
+
+        assert_eq!(diff::<u8>(&[], &[]), []);
+        assert_eq!(diff(&[1], &[]), [Delete(0)]);
+        assert_eq!(diff(&[], &[1]), [Insert(0)]);
+        assert_eq!(
+            diff(&["a", "b", "c"], &["a", "x", "c", "d"]),
+            [Equal(0, 0), Delete(1), Insert(1), Equal(2, 2), Insert(3)],
+        );
+        assert_eq!(
+            diff(&[1, 2, 3, 4], &[3, 4, 1, 2]).len(),
+            // The LCS has length 2.
+            2 + 2 + 2,
+        );
+    }
+
+    #[test]
+    fn edits_reproduce_new() {
+        let old: Vec<char> = "the quick brown fox".chars().collect();
+        let new: Vec<char> = "a quick brown dog jumps".chars().collect();
+        let rebuilt: String = diff(&old, &new)
+            .into_iter()
+            .filter_map(|edit| match edit {
+                Edit::Equal(i, _) => Some(old[i]),
+                Edit::Insert(j) => Some(new[j]),
+                Edit::Delete(_) => None,
+            })
+            .collect();
+        assert_eq!(rebuilt, "a quick brown dog jumps");
+    }
+
+    #[test]
+    fn unified_format() {
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n11\n12\n";
+        let new = "1\n2\nthree\n4\n5\n6\n7\n8\n9\n10\n12\n13\n";
+        // Same as `diff -u` without timestamps.
+        assert_eq!(
+            unified("a", "b", &lines(old, false), &lines(new, false), 3),
+            "\
+--- a
++++ b
+@@ -1,6 +1,6 @@
+ 1
+ 2
+-3
++three
+ 4
+ 5
+ 6
+@@ -8,5 +8,5 @@
+ 8
+ 9
+ 10
+-11
+ 12
++13
+",
+        );
+        // Closer changes are merged into one hunk.
+        assert_eq!(
+            unified("a", "b", &lines(old, false), &lines(new, false), 4)
+                .matches("@@ -")
+                .count(),
+            1,
+        );
+        assert_eq!(
+            unified("a", "b", &lines(old, false), &lines(old, false), 3),
+            ""
+        );
+    }
+
+    #[test]
+    fn unified_insertions_and_deletions_only() {
+        assert_eq!(
+            unified("a", "b", &lines("", false), &lines("x\n", false), 3),
+            "--- a\n+++ b\n@@ -0,0 +1 @@\n+x\n",
+        );
+        assert_eq!(
+            unified("a", "b", &lines("x\ny\n", false), &lines("x\n", false), 0),
+            "--- a\n+++ b\n@@ -2 +1,0 @@\n-y\n",
+        );
+    }
+
+    #[test]
+    fn unified_with_huge_context() {
+        let old = lines("1\n2\n3\n", false);
+        let new = lines("1\nx\n3\n", false);
+        assert_eq!(
+            unified("a", "b", &old, &new, usize::MAX),
+            unified("a", "b", &old, &new, 3),
+        );
+        assert_eq!(
+            unified("a", "b", &old, &new, usize::MAX),
+            "--- a\n+++ b\n@@ -1,3 +1,3 @@\n 1\n-2\n+x\n 3\n",
+        );
+    }
+
+    #[test]
+    fn ignore_comments() {
+        let exercise = "\
+fn main() {
+    // TODO: Fix the compiler error.
+    let x = 5
+  // Another comment
+    println!(\"{x}\"); // Not only a comment
+}
+";
+        let solution = "\
+fn main() {
+    let x = 5;
+    println!(\"{x}\"); // Not only a comment
+}
+";
+        let old = lines(exercise, true);
+        assert_eq!(old.len(), 4);
+        assert_eq!(old[2].number, 5);
+        assert_eq!(
+            unified("a", "b", &old, &lines(solution, true), 3),
+            "\
+--- a
++++ b
+@@ -1,4 +1,4 @@
+ fn main() {
+-    let x = 5
++    let x = 5;
+     println!(\"{x}\"); // Not only a comment
+ }
+",
+        );
+        assert_eq!(lines(exercise, false).len(), 6);
+    }
+}
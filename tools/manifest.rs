@@ -0,0 +1,377 @@
+// Reads the exercises from the `bin` targets in `Cargo.toml`. Only the subset
+// of TOML used by `Cargo.toml` is supported: the inline `bin = [ … ]` array
+// and `[[bin]]` tables with string values for `name` and `path`.
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const EXERCISES_DIR: &str = "exercises";
+pub const SOLUTIONS_DIR: &str = "solutions";
+
+// The directory containing `Cargo.toml`.
+pub fn root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Bin {
+    pub name: String,
+    // Relative to the root.
+    pub path: PathBuf,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ManifestErrorKind {
+    MissingName,
+    MissingPath,
+    UnknownKey(String),
+    // Only double-quoted strings without escapes are supported.
+    InvalidValue(String),
+    UnclosedBinArray,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ManifestError {
+    // 1-based.
+    pub line: usize,
+    pub kind: ManifestErrorKind,
+}
+
+impl fmt::Display for ManifestError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Cargo.toml line {}: ", self.line)?;
+        match &self.kind {
+            ManifestErrorKind::MissingName => f.write_str("bin without `name`"),
+            ManifestErrorKind::MissingPath => f.write_str("bin without `path`"),
+            ManifestErrorKind::UnknownKey(key) => write!(f, "unknown bin key `{key}`"),
+            ManifestErrorKind::InvalidValue(value) => write!(f, "invalid value `{value}`"),
+            ManifestErrorKind::UnclosedBinArray => f.write_str("unclosed `bin` array"),
+        }
+    }
+}
+
+impl Error for ManifestError {}
+
+// The `name` and `path` of one bin while it is being parsed.
+#[derive(Default)]
+struct PartialBin {
+    name: Option<String>,
+    path: Option<PathBuf>,
+}
+
+impl PartialBin {
+    fn set(&mut self, line: usize, key: &str, value: &str) -> Result<(), ManifestError> {
+        let error = |kind| ManifestError { line, kind };
+        let value = value
+            .strip_prefix('"')
+            .and_then(|value| value.strip_suffix('"'))
+            .filter(|value| !value.contains(['"', '\\']))
+            .ok_or_else(|| error(ManifestErrorKind::InvalidValue(value.to_string())))?;
+
+        match key {
+            "name" => self.name = Some(value.to_string()),
+            "path" => self.path = Some(PathBuf::from(value)),
+            _ => return Err(error(ManifestErrorKind::UnknownKey(key.to_string()))),
+        }
+        Ok(())
+    }
+
+    fn finish(self, line: usize) -> Result<Bin, ManifestError> {
+        let error = |kind| ManifestError { line, kind };
+        Ok(Bin {
+            name: self.name.ok_or(error(ManifestErrorKind::MissingName))?,
+            path: self.path.ok_or(error(ManifestErrorKind::MissingPath))?,
+        })
+    }
+}
+
+fn split_key_value(pair: &str) -> Option<(&str, &str)> {
+    let (key, value) = pair.split_once('=')?;
+    Some((key.trim(), value.trim()))
+}
+
+// Returns the bins in the order of the manifest.
+pub fn parse_bins(manifest: &str) -> Result<Vec<Bin>, ManifestError> {
+    let mut bins = Vec::new();
+    let mut in_bin_array = false;
+    // The current `[[bin]]` table and the line of its header.
+    let mut bin_table: Option<(usize, PartialBin)> = None;
+
+    for (index, line) in manifest.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if in_bin_array {
+            if line == "]" {
+                in_bin_array = false;
+                continue;
+            }
+
+            // `{ name = "intro1", path = "exercises/00_intro/intro1.rs" },`
+            let inline_table = line.trim_end_matches(',').trim();
+            let pairs = inline_table
+                .strip_prefix('{')
+                .and_then(|pairs| pairs.strip_suffix('}'))
+                .ok_or_else(|| ManifestError {
+                    line: line_number,
+                    kind: ManifestErrorKind::InvalidValue(inline_table.to_string()),
+                })?;
+            let mut bin = PartialBin::default();
+            for pair in pairs.split(',').filter(|pair| !pair.trim().is_empty()) {
+                let (key, value) = split_key_value(pair).ok_or_else(|| ManifestError {
+                    line: line_number,
+                    kind: ManifestErrorKind::InvalidValue(pair.trim().to_string()),
+                })?;
+                bin.set(line_number, key, value)?;
+            }
+            bins.push(bin.finish(line_number)?);
+            continue;
+        }
+
+        if line.starts_with('[') {
+            if let Some((header_line, bin)) = bin_table.take() {
+                bins.push(bin.finish(header_line)?);
+            }
+            if line == "[[bin]]" {
+                bin_table = Some((line_number, PartialBin::default()));
+            }
+            continue;
+        }
+
+        let Some((key, value)) = split_key_value(line) else {
+            continue;
+        };
+        if let Some((_, bin)) = &mut bin_table {
+            bin.set(line_number, key, value)?;
+        } else if key == "bin" && value == "[" {
+            in_bin_array = true;
+        }
+    }
+
+    if in_bin_array {
+        return Err(ManifestError {
+            line: manifest.lines().count(),
+            kind: ManifestErrorKind::UnclosedBinArray,
+        });
+    }
+    if let Some((header_line, bin)) = bin_table {
+        bins.push(bin.finish(header_line)?);
+    }
+
+    Ok(bins)
+}
+
+pub fn read_bins(root: &Path) -> Result<Vec<Bin>, Box<dyn Error>> {
+    let manifest = fs::read_to_string(root.join("Cargo.toml"))?;
+    Ok(parse_bins(&manifest)?)
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Exercise {
+    pub name: String,
+    // The directory of the exercise, e.g. `18_iterators` or `quizzes`.
+    pub chapter: String,
+    // Relative to the root, e.g. `exercises/18_iterators/iterators1.rs`.
+    pub path: PathBuf,
+}
+
+impl Exercise {
+    // `exercises/<chapter>/<file>` without any other path components.
+    fn from_bin(bin: &Bin) -> Option<Self> {
+        let mut components = bin.path.iter();
+        if components.next()? != EXERCISES_DIR {
+            return None;
+        }
+        let chapter = components.next()?.to_str()?.to_string();
+        components.next()?;
+        if components.next().is_some() {
+            return None;
+        }
+
+        Some(Self {
+            name: bin.name.clone(),
+            chapter,
+            path: bin.path.clone(),
+        })
+    }
+
+    pub fn solution_name(&self) -> String {
+        format!("{}_sol", self.name)
+    }
+
+    pub fn solution_path(&self) -> PathBuf {
+        let file_name = self.path.file_name().unwrap_or_default();
+        Path::new(SOLUTIONS_DIR).join(&self.chapter).join(file_name)
+    }
+}
+
+// The bins of the exercises in the order of the manifest. Solutions and other
+// bins are skipped.
+pub fn exercises(bins: &[Bin]) -> Vec<Exercise> {
+    bins.iter().filter_map(Exercise::from_bin).collect()
+}
+
+pub fn read_exercises(root: &Path) -> Result<Vec<Exercise>, Box<dyn Error>> {
+    Ok(exercises(&read_bins(root)?))
+}
+
+// The topic of a chapter directory without its number, e.g. `iterators` for
+// `18_iterators`.
+pub fn chapter_topic(chapter: &str) -> &str {
+    match chapter.split_once('_') {
+        Some((number, topic)) if number.parse::<u32>().is_ok() => topic,
+        _ => chapter,
+    }
+}
+
+// Groups the exercises by chapter in the order of the first exercise of each
+// chapter.
+pub fn group_by_chapter(exercises: &[Exercise]) -> Vec<(&str, Vec<&Exercise>)> {
+    let mut chapters: Vec<(&str, Vec<&Exercise>)> = Vec::new();
+    for exercise in exercises {
+        match chapters
+            .iter_mut()
+            .find(|(chapter, _)| *chapter == exercise.chapter)
+        {
+            Some((_, chapter_exercises)) => chapter_exercises.push(exercise),
+            None => chapters.push((&exercise.chapter, vec![exercise])),
+        }
+    }
+    chapters
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bin(name: &str, path: &str) -> Bin {
+        Bin {
+            name: name.to_string(),
+            path: PathBuf::from(path),
+        }
+    }
+
+    #[test]
+    fn parse_bin_array() {
+        let manifest = r#"
+bin = [
+  { name = "intro1", path = "exercises/00_intro/intro1.rs" },
+  # A comment
+  { name = "intro1_sol", path = "solutions/00_intro/intro1.rs" }
+]
+
+[package]
+name = "exercises"
+"#;
+        assert_eq!(
+            parse_bins(manifest),
+            Ok(vec![
+                bin("intro1", "exercises/00_intro/intro1.rs"),
+                bin("intro1_sol", "solutions/00_intro/intro1.rs"),
+            ]),
+        );
+    }
+
+    #[test]
+    fn parse_bin_tables() {
+        let manifest = r#"
+[package]
+name = "exercises"
+
+[[bin]]
+name = "if1"
+path = "exercises/03_if/if1.rs"
+
+[[bin]]
+path = "solutions/03_if/if1.rs"
+name = "if1_sol"
+"#;
+        assert_eq!(
+            parse_bins(manifest),
+            Ok(vec![
+                bin("if1", "exercises/03_if/if1.rs"),
+                bin("if1_sol", "solutions/03_if/if1.rs"),
+            ]),
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        let error = |line, kind| Err(ManifestError { line, kind });
+
+        assert_eq!(
+            parse_bins("bin = [\n  { name = \"a\" },\n]"),
+            error(2, ManifestErrorKind::MissingPath),
+        );
+        assert_eq!(
+            parse_bins("[[bin]]\npath = \"a.rs\"\n[package]"),
+            error(1, ManifestErrorKind::MissingName),
+        );
+        assert_eq!(
+            parse_bins("bin = [\n  { name = a, path = \"a.rs\" },\n]"),
+            error(2, ManifestErrorKind::InvalidValue(String::from("a"))),
+        );
+        assert_eq!(
+            parse_bins("[[bin]]\nname = \"a\"\ntest = \"false\""),
+            error(3, ManifestErrorKind::UnknownKey(String::from("test"))),
+        );
+        assert_eq!(
+            parse_bins("bin = [\n  { name = \"a\", path = \"a.rs\" },"),
+            error(2, ManifestErrorKind::UnclosedBinArray),
+        );
+        assert_eq!(
+            parse_bins("bin = [\n{ name = \"a\" }\n]")
+                .unwrap_err()
+                .to_string(),
+            "Cargo.toml line 2: bin without `path`",
+        );
+    }
+
+    #[test]
+    fn exercises_and_chapters() {
+        let bins = [
+            bin("if1", "exercises/03_if/if1.rs"),
+            bin("if1_sol", "solutions/03_if/if1.rs"),
+            bin("quiz1", "exercises/quizzes/quiz1.rs"),
+            bin("if2", "exercises/03_if/if2.rs"),
+            bin("progress", "tools/progress.rs"),
+            bin("nested", "exercises/03_if/nested/if3.rs"),
+        ];
+        let exercises = exercises(&bins);
+        let names: Vec<&str> = exercises.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, ["if1", "quiz1", "if2"]);
+        assert_eq!(exercises[0].chapter, "03_if");
+        assert_eq!(exercises[0].solution_name(), "if1_sol");
+        assert_eq!(
+            exercises[1].solution_path(),
+            Path::new("solutions/quizzes/quiz1.rs"),
+        );
+
+        let chapters = group_by_chapter(&exercises);
+        let chapters: Vec<(&str, usize)> = chapters
+            .iter()
+            .map(|(chapter, exercises)| (*chapter, exercises.len()))
+            .collect();
+        assert_eq!(chapters, [("03_if", 2), ("quizzes", 1)]);
+
+        assert_eq!(chapter_topic("04_primitive_types"), "primitive_types");
+        assert_eq!(chapter_topic("quizzes"), "quizzes");
+        assert_eq!(chapter_topic("move_semantics"), "move_semantics");
+    }
+
+    #[test]
+    fn read_own_manifest() {
+        let bins = read_bins(root()).unwrap();
+        let exercises = exercises(&bins);
+        assert!(exercises.len() > 90);
+        assert_eq!(exercises[0].name, "intro1");
+        assert!(exercises
+            .iter()
+            .all(|e| bins.iter().any(|bin| bin.name == e.solution_name())));
+    }
+}
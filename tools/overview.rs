@@ -0,0 +1,159 @@
+// Prints an overview of the chapters.
+//
+// cargo run --bin overview                # List all chapters
+// cargo run --bin overview -- <chapter>   # E.g. `iterators` or `18_iterators`
+
+mod chapters;
+mod manifest;
+
+use std::env;
+use std::error::Error;
+use std::fmt::Write as _;
+
+use chapters::Chapter;
+
+const USAGE: &str = "usage: overview [<chapter>]";
+
+fn book_sections(chapter: &Chapter) -> String {
+    if chapter.book_sections.is_empty() {
+        String::from("not in the book")
+    } else {
+        format!("Rust Book {}", chapter.book_sections.join(", "))
+    }
+}
+
+fn list(chapters: &[Chapter]) -> String {
+    let width = chapters
+        .iter()
+        .map(|c| c.name.len())
+        .max()
+        .unwrap_or_default();
+    let mut list = String::new();
+    for chapter in chapters {
+        let _ = writeln!(
+            list,
+            "{:width$}  {:>2} exercises  {}",
+            chapter.name,
+            chapter.exercises.len(),
+            book_sections(chapter),
+        );
+    }
+    list
+}
+
+fn overview(chapter: &Chapter) -> String {
+    let mut overview = format!("{} ({})\n", chapter.name, book_sections(chapter));
+
+    if !chapter.links.is_empty() {
+        overview.push_str("\nFurther information:\n");
+        for link in &chapter.links {
+            let _ = writeln!(overview, "- {}: {}", link.title, link.url);
+        }
+    }
+
+    overview.push_str("\nExercises:\n");
+    for exercise in &chapter.exercises {
+        let _ = writeln!(
+            overview,
+            "- {} ({})",
+            exercise.name,
+            exercise.path.display()
+        );
+    }
+
+    overview
+}
+
+// Finds a chapter by its directory or its topic.
+fn find<'a>(chapters: &'a [Chapter], name: &str) -> Option<&'a Chapter> {
+    chapters
+        .iter()
+        .find(|chapter| chapter.name == name || chapter.topic() == name)
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let chapters = chapters::read_index(manifest::root())?;
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    match &args[..] {
+        [] => print!("{}", list(&chapters)),
+        [name] => {
+            let chapter =
+                find(&chapters, name).ok_or_else(|| format!("unknown chapter `{name}`"))?;
+            print!("{}", overview(chapter));
+        }
+        _ => return Err(USAGE.into()),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chapters::Link;
+    use manifest::Exercise;
+    use std::path::PathBuf;
+
+    fn chapters() -> Vec<Chapter> {
+        let exercise = |name: &str, chapter: &str| Exercise {
+            name: name.to_string(),
+            chapter: chapter.to_string(),
+            path: PathBuf::from(format!("exercises/{chapter}/{name}.rs")),
+        };
+        vec![
+            Chapter {
+                name: String::from("04_primitive_types"),
+                book_sections: vec![String::from("§3.2"), String::from("§4.3")],
+                links: vec![Link {
+                    title: String::from("Data Types"),
+                    url: String::from("https://doc.rust-lang.org/book/ch03-02-data-types.html"),
+                }],
+                exercises: vec![
+                    exercise("primitive_types1", "04_primitive_types"),
+                    exercise("primitive_types2", "04_primitive_types"),
+                ],
+            },
+            Chapter {
+                name: String::from("quizzes"),
+                book_sections: Vec::new(),
+                links: Vec::new(),
+                exercises: vec![exercise("quiz1", "quizzes")],
+            },
+        ]
+    }
+
+    #[test]
+    fn list_chapters() {
+        assert_eq!(
+            list(&chapters()),
+            "\
+04_primitive_types   2 exercises  Rust Book §3.2, §4.3
+quizzes              1 exercises  not in the book
+",
+        );
+    }
+
+    #[test]
+    fn chapter_overview() {
+        let chapters = chapters();
+        assert_eq!(
+            overview(find(&chapters, "primitive_types").unwrap()),
+            "\
+04_primitive_types (Rust Book §3.2, §4.3)
+
+Further information:
+- Data Types: https://doc.rust-lang.org/book/ch03-02-data-types.html
+
+Exercises:
+- primitive_types1 (exercises/04_primitive_types/primitive_types1.rs)
+- primitive_types2 (exercises/04_primitive_types/primitive_types2.rs)
+",
+        );
+        assert_eq!(
+            overview(find(&chapters, "quizzes").unwrap()),
+            "quizzes (not in the book)\n\nExercises:\n- quiz1 (exercises/quizzes/quiz1.rs)\n",
+        );
+        assert!(find(&chapters, "04").is_none());
+    }
+}
@@ -0,0 +1,430 @@
+// Checks that every exercise has a solution and that `Cargo.toml` lists both.
+//
+// cargo run --bin parity           # Check the bins, files and chapter order
+// cargo run --bin parity -- --run  # Also check that the solutions pass and the
+//                                  # exercises don't
+
+mod cargo;
+mod chapters;
+mod manifest;
+
+use std::collections::HashSet;
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use manifest::{Bin, Exercise, EXERCISES_DIR, SOLUTIONS_DIR};
+
+const USAGE: &str = "usage: parity [--run]";
+
+#[derive(Debug, PartialEq, Eq)]
+enum Problem {
+    DuplicateBin(String),
+    MissingFile {
+        bin: String,
+        path: PathBuf,
+    },
+    // An exercise without a `<name>_sol` bin.
+    MissingSolutionBin(String),
+    WrongSolutionPath {
+        bin: String,
+        expected: PathBuf,
+        found: PathBuf,
+    },
+    // A bin in `solutions/` without an exercise bin.
+    UnpairedSolutionBin(String),
+    UnregisteredFile(PathBuf),
+    // The first chapter that differs from `exercises/README.md`. `None` if
+    // one list ends before the other.
+    ChapterOrder {
+        position: usize,
+        readme: Option<String>,
+        manifest: Option<String>,
+    },
+    SolutionFails(String),
+    ExercisePasses(String),
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::DuplicateBin(bin) => write!(f, "bin `{bin}` is listed more than once"),
+            Self::MissingFile { bin, path } => {
+                write!(f, "bin `{bin}`: `{}` doesn't exist", path.display())
+            }
+            Self::MissingSolutionBin(bin) => write!(f, "exercise `{bin}` has no `{bin}_sol` bin"),
+            Self::WrongSolutionPath {
+                bin,
+                expected,
+                found,
+            } => write!(
+                f,
+                "bin `{bin}`: expected path `{}`, found `{}`",
+                expected.display(),
+                found.display(),
+            ),
+            Self::UnpairedSolutionBin(bin) => write!(f, "solution `{bin}` has no exercise bin"),
+            Self::UnregisteredFile(path) => {
+                write!(f, "`{}` isn't listed in Cargo.toml", path.display())
+            }
+            Self::ChapterOrder {
+                position,
+                readme,
+                manifest,
+            } => {
+                let name = |chapter: &Option<String>| match chapter {
+                    Some(chapter) => format!("`{chapter}`"),
+                    None => String::from("nothing"),
+                };
+                write!(
+                    f,
+                    "chapter {}: exercises/README.md lists {}, Cargo.toml lists {}",
+                    position + 1,
+                    name(readme),
+                    name(manifest),
+                )
+            }
+            Self::SolutionFails(bin) => write!(f, "solution `{bin}` fails"),
+            Self::ExercisePasses(bin) => write!(f, "exercise `{bin}` already passes"),
+        }
+    }
+}
+
+// Checks the names and paths of the bins. `exists` tells whether a path
+// relative to the root exists.
+fn check_bins(bins: &[Bin], exists: impl Fn(&Path) -> bool) -> Vec<Problem> {
+    let mut problems = Vec::new();
+
+    let mut names = HashSet::new();
+    for bin in bins {
+        if !names.insert(&bin.name) {
+            problems.push(Problem::DuplicateBin(bin.name.clone()));
+        }
+        if !exists(&bin.path) {
+            problems.push(Problem::MissingFile {
+                bin: bin.name.clone(),
+                path: bin.path.clone(),
+            });
+        }
+    }
+
+    let exercises = manifest::exercises(bins);
+    for exercise in &exercises {
+        let solution_name = exercise.solution_name();
+        match bins.iter().find(|bin| bin.name == solution_name) {
+            None => problems.push(Problem::MissingSolutionBin(exercise.name.clone())),
+            Some(solution) if solution.path != exercise.solution_path() => {
+                problems.push(Problem::WrongSolutionPath {
+                    bin: solution_name,
+                    expected: exercise.solution_path(),
+                    found: solution.path.clone(),
+                });
+            }
+            Some(_) => (),
+        }
+    }
+
+    for bin in bins {
+        if bin.path.starts_with(SOLUTIONS_DIR)
+            && !exercises.iter().any(|e| e.solution_name() == bin.name)
+        {
+            problems.push(Problem::UnpairedSolutionBin(bin.name.clone()));
+        }
+    }
+
+    problems
+}
+
+// Checks that every `.rs` file in `files` is listed in `Cargo.toml`.
+fn check_files(bins: &[Bin], files: &[PathBuf]) -> Vec<Problem> {
+    files
+        .iter()
+        .filter(|file| !bins.iter().any(|bin| bin.path == **file))
+        .map(|file| Problem::UnregisteredFile(file.clone()))
+        .collect()
+}
+
+// The topics of the chapters that `exercises/README.md` doesn't list.
+const UNLISTED_TOPICS: [&str; 2] = ["intro", "quizzes"];
+
+// Compares the order of the chapters in `Cargo.toml` with `exercises/README.md`.
+fn check_chapter_order(
+    exercises: &[Exercise],
+    book_sections: &[(String, Vec<String>)],
+) -> Option<Problem> {
+    let readme_topics: Vec<&str> = book_sections
+        .iter()
+        .map(|(topic, _)| topic.as_str())
+        .collect();
+    let manifest_topics: Vec<&str> = manifest::group_by_chapter(exercises)
+        .into_iter()
+        .map(|(chapter, _)| manifest::chapter_topic(chapter))
+        .filter(|topic| !UNLISTED_TOPICS.contains(topic))
+        .collect();
+
+    let length = manifest_topics.len().max(readme_topics.len());
+    (0..length)
+        .find(|&i| manifest_topics.get(i) != readme_topics.get(i))
+        .map(|position| Problem::ChapterOrder {
+            position,
+            readme: readme_topics.get(position).map(|s| s.to_string()),
+            manifest: manifest_topics.get(position).map(|s| s.to_string()),
+        })
+}
+
+// The `.rs` files in the chapter directories of `exercises/` and `solutions/`
+// relative to the root, sorted.
+fn chapter_files(root: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for dir in [EXERCISES_DIR, SOLUTIONS_DIR] {
+        for chapter in fs::read_dir(root.join(dir))? {
+            let chapter = chapter?;
+            if !chapter.file_type()?.is_dir() {
+                continue;
+            }
+            for file in fs::read_dir(chapter.path())? {
+                let path = file?.path();
+                if path.extension().is_some_and(|extension| extension == "rs") {
+                    files.push(
+                        Path::new(dir)
+                            .join(chapter.file_name())
+                            .join(path.file_name().unwrap_or_default()),
+                    );
+                }
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+// The exercises that pass without changes by design, like the ones with
+// `skip_check_unsolved = true` in the `info.toml` of rustlings.
+const SKIP_CHECK_UNSOLVED: [&str; 1] = ["intro1"];
+
+// Runs each solution and each exercise with `check`, which returns whether a
+// bin passes. Prints one line per exercise because this takes a while.
+fn check_runs<F>(exercises: &[Exercise], mut check: F) -> io::Result<Vec<Problem>>
+where
+    F: FnMut(&str, &Path) -> io::Result<bool>,
+{
+    let mut problems = Vec::new();
+    for exercise in exercises {
+        println!("Checking {}…", exercise.name);
+        let solution_name = exercise.solution_name();
+        if !check(&solution_name, &exercise.solution_path())? {
+            problems.push(Problem::SolutionFails(solution_name));
+        }
+        if SKIP_CHECK_UNSOLVED.contains(&exercise.name.as_str()) {
+            continue;
+        }
+        if check(&exercise.name, &exercise.path)? {
+            problems.push(Problem::ExercisePasses(exercise.name.clone()));
+        }
+    }
+    Ok(problems)
+}
+
+fn main() -> Result<ExitCode, Box<dyn Error>> {
+    let run = match env::args().skip(1).collect::<Vec<_>>()[..] {
+        [] => false,
+        [ref flag] if flag == "--run" => true,
+        _ => return Err(USAGE.into()),
+    };
+
+    let root = manifest::root();
+    let bins = manifest::read_bins(root)?;
+    let exercises = manifest::exercises(&bins);
+    let readme = fs::read_to_string(root.join(EXERCISES_DIR).join("README.md"))?;
+
+    let mut problems = check_bins(&bins, |path| root.join(path).is_file());
+    problems.extend(check_files(&bins, &chapter_files(root)?));
+    problems.extend(check_chapter_order(
+        &exercises,
+        &chapters::parse_book_sections(&readme),
+    ));
+    // Running is pointless if the bins are broken.
+    if run && problems.is_empty() {
+        problems.extend(check_runs(&exercises, |bin, path| {
+            Ok(cargo::check_bin(root, bin, path)?.success)
+        })?);
+    }
+
+    for problem in &problems {
+        println!("{problem}");
+    }
+    if problems.is_empty() {
+        println!("{} exercises and solutions are in sync", exercises.len());
+        Ok(ExitCode::SUCCESS)
+    } else {
+        println!("{} problems found", problems.len());
+        Ok(ExitCode::FAILURE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bin(name: &str, path: &str) -> Bin {
+        Bin {
+            name: name.to_string(),
+            path: PathBuf::from(path),
+        }
+    }
+
+    fn pair(name: &str, chapter: &str) -> [Bin; 2] {
+        [
+            bin(name, &format!("exercises/{chapter}/{name}.rs")),
+            bin(
+                &format!("{name}_sol"),
+                &format!("solutions/{chapter}/{name}.rs"),
+            ),
+        ]
+    }
+
+    #[test]
+    fn complete_mapping() {
+        let bins = [pair("if1", "03_if"), pair("quiz1", "quizzes")].concat();
+        assert!(check_bins(&bins, |_| true).is_empty());
+
+        let files: Vec<PathBuf> = bins.iter().map(|bin| bin.path.clone()).collect();
+        assert!(check_files(&bins, &files).is_empty());
+    }
+
+    #[test]
+    fn broken_bins() {
+        let mut bins = pair("if1", "03_if").to_vec();
+        bins.push(bin("if1", "exercises/03_if/if1.rs"));
+        bins.push(bin("if2", "exercises/03_if/if2.rs"));
+        bins.push(bin("if3", "exercises/03_if/if3.rs"));
+        bins.push(bin("if3_sol", "solutions/03_if/if2.rs"));
+        bins.push(bin("if4_sol", "solutions/03_if/if4.rs"));
+        bins.push(bin("progress", "tools/progress.rs"));
+
+        let problems = check_bins(&bins, |path| !path.ends_with("if4.rs"));
+        assert_eq!(
+            problems,
+            [
+                Problem::DuplicateBin(String::from("if1")),
+                Problem::MissingFile {
+                    bin: String::from("if4_sol"),
+                    path: PathBuf::from("solutions/03_if/if4.rs"),
+                },
+                Problem::MissingSolutionBin(String::from("if2")),
+                Problem::WrongSolutionPath {
+                    bin: String::from("if3_sol"),
+                    expected: PathBuf::from("solutions/03_if/if3.rs"),
+                    found: PathBuf::from("solutions/03_if/if2.rs"),
+                },
+                Problem::UnpairedSolutionBin(String::from("if4_sol")),
+            ],
+        );
+    }
+
+    #[test]
+    fn unregistered_files() {
+        let bins = pair("if1", "03_if");
+        let files = [
+            PathBuf::from("exercises/03_if/if1.rs"),
+            PathBuf::from("exercises/03_if/if2.rs"),
+            PathBuf::from("solutions/03_if/if1.rs"),
+        ];
+        assert_eq!(
+            check_files(&bins, &files),
+            [Problem::UnregisteredFile(PathBuf::from(
+                "exercises/03_if/if2.rs"
+            ))],
+        );
+    }
+
+    #[test]
+    fn chapter_order() {
+        let readme = "\
+| Exercise  | Book Chapter |
+| --------- | ------------ |
+| variables | §3.1         |
+| if        | §3.5         |
+| vecs      | §8.1         |
+";
+        let sections = chapters::parse_book_sections(readme);
+
+        let bins = [
+            pair("intro1", "00_intro"),
+            pair("variables1", "01_variables"),
+            pair("if1", "03_if"),
+            pair("quiz1", "quizzes"),
+            pair("vecs1", "05_vecs"),
+        ]
+        .concat();
+        let exercises = manifest::exercises(&bins);
+        assert_eq!(check_chapter_order(&exercises, &sections), None);
+
+        let mut swapped = sections.clone();
+        swapped.swap(1, 2);
+        assert_eq!(
+            check_chapter_order(&exercises, &swapped),
+            Some(Problem::ChapterOrder {
+                position: 1,
+                readme: Some(String::from("vecs")),
+                manifest: Some(String::from("if")),
+            }),
+        );
+        let problem = check_chapter_order(&exercises[..3], &sections).unwrap();
+        assert_eq!(
+            problem.to_string(),
+            "chapter 3: exercises/README.md lists `vecs`, Cargo.toml lists nothing",
+        );
+    }
+
+    #[test]
+    fn runs() {
+        let bins = [pair("intro1", "00_intro"), pair("if1", "03_if")].concat();
+        let exercises = manifest::exercises(&bins);
+
+        // Everything passes, but intro1 is expected to.
+        let mut checked = Vec::new();
+        let problems = check_runs(&exercises, |bin, _| {
+            checked.push(bin.to_string());
+            Ok(true)
+        })
+        .unwrap();
+        assert_eq!(problems, [Problem::ExercisePasses(String::from("if1"))]);
+        assert_eq!(checked, ["intro1_sol", "if1_sol", "if1"]);
+
+        let problems = check_runs(&exercises, |bin, _| Ok(!bin.ends_with("_sol"))).unwrap();
+        assert_eq!(
+            problems,
+            [
+                Problem::SolutionFails(String::from("intro1_sol")),
+                Problem::SolutionFails(String::from("if1_sol")),
+                Problem::ExercisePasses(String::from("if1")),
+            ],
+        );
+        assert_eq!(
+            check_runs(&exercises, |bin, _| Ok(bin.ends_with("_sol"))).unwrap(),
+            [],
+        );
+    }
+
+    #[test]
+    fn own_exercises_are_in_sync() {
+        let root = manifest::root();
+        let bins = manifest::read_bins(root).unwrap();
+        let readme = fs::read_to_string(root.join("exercises/README.md")).unwrap();
+
+        assert_eq!(check_bins(&bins, |path| root.join(path).is_file()), []);
+        assert_eq!(check_files(&bins, &chapter_files(root).unwrap()), []);
+        assert_eq!(
+            check_chapter_order(
+                &manifest::exercises(&bins),
+                &chapters::parse_book_sections(&readme)
+            ),
+            None,
+        );
+    }
+}
@@ -0,0 +1,212 @@
+// Tracks the progress of the exercises listed in `Cargo.toml`. The exercises
+// done in the rustlings CLI count as complete unless set otherwise.
+//
+// cargo run --bin progress                          # Print the progress per chapter
+// cargo run --bin progress -- set <exercise> <none|some|complete>
+// cargo run --bin progress -- reset                 # Forget the progress set here
+
+mod manifest;
+mod state;
+
+use std::collections::HashMap;
+use std::env;
+use std::error::Error;
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use manifest::Exercise;
+use state::{count_collection_iterator, count_iterator, Progress};
+
+const USAGE: &str = "usage: progress [set <exercise> <none|some|complete> | reset]";
+
+// The progress of each exercise of each chapter in the order of the chapters.
+// Exercises missing from `state` haven't been started.
+fn chapter_progress(
+    exercises: &[Exercise],
+    state: &HashMap<String, Progress>,
+) -> Vec<(String, HashMap<String, Progress>)> {
+    manifest::group_by_chapter(exercises)
+        .into_iter()
+        .map(|(chapter, exercises)| {
+            let progress = exercises
+                .into_iter()
+                .map(|exercise| {
+                    let progress = state.get(&exercise.name).copied().unwrap_or(Progress::None);
+                    (exercise.name.clone(), progress)
+                })
+                .collect();
+            (chapter.to_string(), progress)
+        })
+        .collect()
+}
+
+fn report(chapters: &[(String, HashMap<String, Progress>)]) -> String {
+    let width = chapters
+        .iter()
+        .map(|(chapter, _)| chapter.len())
+        .chain(["Total".len()])
+        .max()
+        .unwrap_or_default();
+
+    // The cells of a row are "complete/total", "some" and "none".
+    let counts = |complete: usize, some: usize, none: usize| {
+        let total = complete + some + none;
+        [
+            format!("{complete}/{total}"),
+            some.to_string(),
+            none.to_string(),
+        ]
+    };
+    let mut report = String::new();
+    let mut row = |name: &str, [done, started, pending]: [String; 3]| {
+        let _ = writeln!(
+            report,
+            "{name:width$}  {done:>7}  {started:>7}  {pending:>7}"
+        );
+    };
+
+    row("Chapter", ["Done", "Started", "Pending"].map(String::from));
+    for (chapter, progress) in chapters {
+        row(
+            chapter,
+            counts(
+                count_iterator(progress, Progress::Complete),
+                count_iterator(progress, Progress::Some),
+                count_iterator(progress, Progress::None),
+            ),
+        );
+    }
+
+    let all: Vec<HashMap<String, Progress>> = chapters
+        .iter()
+        .map(|(_, progress)| progress.clone())
+        .collect();
+    row(
+        "Total",
+        counts(
+            count_collection_iterator(&all, Progress::Complete),
+            count_collection_iterator(&all, Progress::Some),
+            count_collection_iterator(&all, Progress::None),
+        ),
+    );
+
+    report
+}
+
+// Only `STATE_FILE` is updated, without the seed from the rustlings CLI.
+fn set_progress(root: &Path, exercise: &str, progress: Progress) -> Result<(), Box<dyn Error>> {
+    let state_path = state::state_path(root);
+    let mut state = state::load_state(&state_path)?;
+    state.insert(exercise.to_string(), progress);
+    state::save_state(&state_path, &state)?;
+    Ok(())
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let root = manifest::root();
+    let exercises = manifest::read_exercises(root)?;
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    match args[..] {
+        [] => {
+            let state = state::load_seeded_state(root)?;
+            print!("{}", report(&chapter_progress(&exercises, &state)));
+        }
+        ["set", exercise, progress] => {
+            if !exercises.iter().any(|e| e.name == exercise) {
+                return Err(format!("unknown exercise `{exercise}`").into());
+            }
+            let progress = state::parse_progress(progress)
+                .ok_or_else(|| format!("unknown progress `{progress}`"))?;
+            set_progress(root, exercise, progress)?;
+        }
+        ["reset"] => match fs::remove_file(state::state_path(root)) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e.into()),
+            _ => (),
+        },
+        _ => return Err(USAGE.into()),
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn exercise(name: &str, chapter: &str) -> Exercise {
+        Exercise {
+            name: name.to_string(),
+            chapter: chapter.to_string(),
+            path: PathBuf::from(format!("exercises/{chapter}/{name}.rs")),
+        }
+    }
+
+    fn exercises() -> Vec<Exercise> {
+        vec![
+            exercise("if1", "03_if"),
+            exercise("if2", "03_if"),
+            exercise("if3", "03_if"),
+            exercise("quiz1", "quizzes"),
+            exercise("vecs1", "05_vecs"),
+        ]
+    }
+
+    #[test]
+    fn progress_per_chapter() {
+        let state = state::parse_state("if1 complete\nif2 some\nvecs1 complete\nremoved1 complete")
+            .unwrap();
+        let chapters = chapter_progress(&exercises(), &state);
+
+        let names: Vec<&str> = chapters.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["03_if", "quizzes", "05_vecs"]);
+        assert_eq!(chapters[0].1.len(), 3);
+        assert_eq!(chapters[0].1["if3"], Progress::None);
+        assert_eq!(chapters[1].1["quiz1"], Progress::None);
+        // Exercises that were removed from `Cargo.toml` are ignored.
+        assert!(chapters.iter().all(|(_, p)| !p.contains_key("removed1")));
+    }
+
+    #[test]
+    fn report_counts() {
+        let state = state::parse_state("if1 complete\nif2 some\nvecs1 complete").unwrap();
+        let report = report(&chapter_progress(&exercises(), &state));
+        assert_eq!(
+            report,
+            "\
+Chapter     Done  Started  Pending
+03_if        1/3        1        1
+quizzes      0/1        0        1
+05_vecs      1/1        0        0
+Total        2/5        1        2
+",
+        );
+    }
+
+    #[test]
+    fn set_keeps_the_seed_out_of_the_state_file() {
+        let root = std::env::temp_dir().join(format!("progress_set_{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        fs::write(
+            root.join(state::RUSTLINGS_STATE_FILE),
+            "DON'T EDIT THIS FILE!\n\nif3\n\nif1\nif2\n",
+        )
+        .unwrap();
+        fs::write(state::state_path(&root), "if2 some\n").unwrap();
+
+        let set = set_progress(&root, "vecs1", Progress::Complete);
+        let state_file = fs::read_to_string(state::state_path(&root));
+        let seeded = state::load_seeded_state(&root);
+        fs::remove_dir_all(&root).unwrap();
+
+        set.unwrap();
+        assert_eq!(state_file.unwrap(), "if2 some\nvecs1 complete\n");
+        let report = report(&chapter_progress(&exercises(), &seeded.unwrap()));
+        assert!(report.contains("03_if        1/3        1        1\n"));
+        assert!(report.contains("05_vecs      1/1        0        0\n"));
+    }
+}
@@ -0,0 +1,209 @@
+// Runs exercises with the right Cargo command and records the result in the
+// progress state of `tools/progress.rs`.
+//
+// cargo run --bin runner -- <exercise>   # Run or test one exercise
+// cargo run --bin runner -- --next       # Find the next pending exercise
+
+mod cargo;
+mod manifest;
+mod state;
+
+use std::collections::HashMap;
+use std::env;
+use std::error::Error;
+use std::io;
+use std::process::ExitCode;
+
+use cargo::{Mode, Outcome};
+use manifest::Exercise;
+use state::Progress;
+
+const USAGE: &str = "usage: runner <exercise> | runner --next";
+
+// Passing exercises are complete, failing ones are started.
+fn record(state: &mut HashMap<String, Progress>, exercise: &Exercise, outcome: &Outcome) {
+    let progress = if outcome.success {
+        Progress::Complete
+    } else {
+        Progress::Some
+    };
+    state.insert(exercise.name.clone(), progress);
+}
+
+// Checks the exercises in order, skipping the complete ones, until one fails.
+// An exercise is complete in `state` or, without an entry there, in the `seed`
+// from the rustlings CLI. Only the checked exercises are recorded in `state`.
+// Returns `None` if all exercises pass.
+fn next_pending<'a, F>(
+    exercises: &'a [Exercise],
+    seed: &HashMap<String, Progress>,
+    state: &mut HashMap<String, Progress>,
+    mut check: F,
+) -> io::Result<Option<(&'a Exercise, Outcome)>>
+where
+    F: FnMut(&Exercise) -> io::Result<Outcome>,
+{
+    for exercise in exercises {
+        let progress = state.get(&exercise.name).or(seed.get(&exercise.name));
+        if progress == Some(&Progress::Complete) {
+            continue;
+        }
+
+        let outcome = check(exercise)?;
+        record(state, exercise, &outcome);
+        if !outcome.success {
+            return Ok(Some((exercise, outcome)));
+        }
+    }
+    Ok(None)
+}
+
+fn summary(exercise: &Exercise, outcome: &Outcome) -> String {
+    let command = match outcome.mode {
+        Mode::Run => "run",
+        Mode::Test => "test",
+    };
+    let result = if outcome.success { "passed" } else { "failed" };
+    format!(
+        "{} ({}): `cargo {command}` {result}",
+        exercise.name,
+        exercise.path.display(),
+    )
+}
+
+fn main() -> Result<ExitCode, Box<dyn Error>> {
+    let root = manifest::root();
+    let exercises = manifest::read_exercises(root)?;
+    let state_path = state::state_path(root);
+    // Only the local state is saved, see `state::seeded`.
+    let mut state = state::load_state(&state_path)?;
+    let check = |exercise: &Exercise| cargo::check_bin(root, &exercise.name, &exercise.path);
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let (exercise, outcome) = match args[..] {
+        ["--next"] => {
+            let seed = state::load_seed(root)?;
+            let pending = next_pending(&exercises, &seed, &mut state, check)?;
+            state::save_state(&state_path, &state)?;
+            match pending {
+                Some(pending) => pending,
+                None => {
+                    println!("All exercises are complete!");
+                    return Ok(ExitCode::SUCCESS);
+                }
+            }
+        }
+        [name] if !name.starts_with('-') => {
+            let exercise = exercises
+                .iter()
+                .find(|e| e.name == name)
+                .ok_or_else(|| format!("unknown exercise `{name}`"))?;
+            let outcome = check(exercise)?;
+            record(&mut state, exercise, &outcome);
+            state::save_state(&state_path, &state)?;
+            (exercise, outcome)
+        }
+        _ => return Err(USAGE.into()),
+    };
+
+    print!("{}", outcome.output);
+    println!("{}", summary(exercise, &outcome));
+    Ok(if outcome.success {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn exercise(name: &str) -> Exercise {
+        Exercise {
+            name: name.to_string(),
+            chapter: String::from("03_if"),
+            path: PathBuf::from(format!("exercises/03_if/{name}.rs")),
+        }
+    }
+
+    fn outcome(success: bool) -> Outcome {
+        Outcome {
+            mode: Mode::Test,
+            success,
+            output: String::new(),
+        }
+    }
+
+    #[test]
+    fn next_pending_skips_complete_exercises() {
+        let exercises = [exercise("if1"), exercise("if2"), exercise("if3")];
+        let mut state = state::parse_state("if1 complete").unwrap();
+
+        let mut checked = Vec::new();
+        let pending = next_pending(&exercises, &HashMap::new(), &mut state, |e| {
+            checked.push(e.name.clone());
+            Ok(outcome(e.name != "if3"))
+        })
+        .unwrap();
+
+        let (pending, outcome) = pending.unwrap();
+        assert_eq!(pending.name, "if3");
+        assert!(!outcome.success);
+        assert_eq!(checked, ["if2", "if3"]);
+        assert_eq!(state["if2"], Progress::Complete);
+        assert_eq!(state["if3"], Progress::Some);
+    }
+
+    #[test]
+    fn next_pending_when_all_pass() {
+        let exercises = [exercise("if1"), exercise("if2")];
+        let mut state = HashMap::new();
+        let pending = next_pending(&exercises, &HashMap::new(), &mut state, |_| {
+            Ok(outcome(true))
+        })
+        .unwrap();
+        assert!(pending.is_none());
+        assert_eq!(state.len(), 2);
+    }
+
+    #[test]
+    fn next_pending_stops_at_io_errors() {
+        let exercises = [exercise("if1"), exercise("if2")];
+        let mut state = HashMap::new();
+        let result = next_pending(&exercises, &HashMap::new(), &mut state, |_| {
+            Err(io::Error::new(io::ErrorKind::NotFound, "no cargo"))
+        });
+        assert!(result.is_err());
+        assert!(state.is_empty());
+    }
+
+    #[test]
+    fn next_pending_keeps_the_seed_out_of_the_state() {
+        let exercises = [exercise("if1"), exercise("if2"), exercise("if3")];
+        let seed = state::parse_state("if1 complete\nif2 complete").unwrap();
+        // The local state takes precedence over the seed.
+        let mut state = state::parse_state("if2 some").unwrap();
+
+        let mut checked = Vec::new();
+        let pending = next_pending(&exercises, &seed, &mut state, |e| {
+            checked.push(e.name.clone());
+            Ok(outcome(true))
+        })
+        .unwrap();
+
+        assert!(pending.is_none());
+        assert_eq!(checked, ["if2", "if3"]);
+        assert_eq!(state::render_state(&state), "if2 complete\nif3 complete\n");
+    }
+
+    #[test]
+    fn summary_line() {
+        assert_eq!(
+            summary(&exercise("if1"), &outcome(false)),
+            "if1 (exercises/03_if/if1.rs): `cargo test` failed",
+        );
+    }
+}
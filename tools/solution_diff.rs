@@ -0,0 +1,136 @@
+// Prints the differences between an exercise and its solution as a unified
+// diff.
+//
+// cargo run --bin solution_diff -- <exercise> [--ignore-comments] [--context <lines>]
+//
+// `--ignore-comments` skips the lines that only contain a comment, like the
+// `TODO` comments of the exercises.
+
+mod diff;
+mod manifest;
+
+use std::env;
+use std::error::Error;
+use std::fs;
+
+const USAGE: &str = "usage: solution_diff <exercise> [--ignore-comments] [--context <lines>]";
+const DEFAULT_CONTEXT: usize = 3;
+
+#[derive(Debug, PartialEq, Eq)]
+struct Options {
+    exercise: String,
+    ignore_comments: bool,
+    context: usize,
+}
+
+fn parse_args(args: impl IntoIterator<Item = String>) -> Option<Options> {
+    let mut exercise = None;
+    let mut ignore_comments = false;
+    let mut context = DEFAULT_CONTEXT;
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--ignore-comments" => ignore_comments = true,
+            "--context" => context = args.next()?.parse().ok()?,
+            _ if arg.starts_with('-') || exercise.is_some() => return None,
+            _ => exercise = Some(arg),
+        }
+    }
+
+    Some(Options {
+        exercise: exercise?,
+        ignore_comments,
+        context,
+    })
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let options = parse_args(env::args().skip(1)).ok_or(USAGE)?;
+
+    let root = manifest::root();
+    let exercises = manifest::read_exercises(root)?;
+    let exercise = exercises
+        .iter()
+        .find(|e| e.name == options.exercise)
+        .ok_or_else(|| format!("unknown exercise `{}`", options.exercise))?;
+
+    let solution_path = exercise.solution_path();
+    let exercise_text = fs::read_to_string(root.join(&exercise.path))?;
+    let solution_text = fs::read_to_string(root.join(&solution_path))?;
+
+    let output = diff::unified(
+        &exercise.path.display().to_string(),
+        &solution_path.display().to_string(),
+        &diff::lines(&exercise_text, options.ignore_comments),
+        &diff::lines(&solution_text, options.ignore_comments),
+        options.context,
+    );
+    if output.is_empty() {
+        println!("The exercise matches its solution");
+    } else {
+        print!("{output}");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Option<Options> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn args() {
+        assert_eq!(
+            parse(&["if1"]),
+            Some(Options {
+                exercise: String::from("if1"),
+                ignore_comments: false,
+                context: DEFAULT_CONTEXT,
+            }),
+        );
+        assert_eq!(
+            parse(&["--context", "0", "if1", "--ignore-comments"]),
+            Some(Options {
+                exercise: String::from("if1"),
+                ignore_comments: true,
+                context: 0,
+            }),
+        );
+        assert_eq!(parse(&[]), None);
+        assert_eq!(parse(&["if1", "if2"]), None);
+        assert_eq!(parse(&["if1", "--context"]), None);
+        assert_eq!(parse(&["if1", "--context", "-1"]), None);
+        assert_eq!(parse(&["if1", "--verbose"]), None);
+    }
+
+    #[test]
+    fn diff_own_exercise() {
+        let root = manifest::root();
+        let exercise = fs::read_to_string(root.join("exercises/03_if/if1.rs")).unwrap();
+        let solution = fs::read_to_string(root.join("solutions/03_if/if1.rs")).unwrap();
+
+        let with_comments = diff::unified(
+            "a",
+            "b",
+            &diff::lines(&exercise, false),
+            &diff::lines(&solution, false),
+            3,
+        );
+        assert!(with_comments.contains("-    // TODO"));
+
+        let without_comments = diff::unified(
+            "a",
+            "b",
+            &diff::lines(&exercise, true),
+            &diff::lines(&solution, true),
+            3,
+        );
+        assert!(!without_comments.contains("TODO"));
+        assert!(without_comments.len() < with_comments.len());
+    }
+}
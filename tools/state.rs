@@ -0,0 +1,301 @@
+// Stores the `Progress` of each exercise in a local text file with one
+// `<exercise> <progress>` line per exercise. Exercises without a line haven't
+// been started.
+//
+// The rustlings CLI keeps its own `.rustlings-state.txt`, but it only records
+// the done exercises and the current one. This separate file also records the
+// started exercises, and keeps the tools from corrupting the file of the CLI,
+// which is only read to seed the progress.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+// Relative to the root. Ignored by Git.
+pub const STATE_FILE: &str = ".exercise-progress.txt";
+// The state of the rustlings CLI, relative to the root.
+pub const RUSTLINGS_STATE_FILE: &str = ".rustlings-state.txt";
+
+const RUSTLINGS_STATE_HEADER: &str = "DON'T EDIT THIS FILE!";
+
+// The same model as in the `iterators5` exercise. It is a copy because the
+// solution keeps its items private like the exercise does, so a `#[path]`
+// module couldn't use them. `same_model_as_iterators5` keeps the copies equal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Progress {
+    None,
+    Some,
+    Complete,
+}
+
+pub fn count_iterator(map: &HashMap<String, Progress>, value: Progress) -> usize {
+    map.values().filter(|val| **val == value).count()
+}
+
+pub fn count_collection_iterator(
+    collection: &[HashMap<String, Progress>],
+    value: Progress,
+) -> usize {
+    collection
+        .iter()
+        .map(|map| count_iterator(map, value))
+        .sum()
+}
+
+pub fn state_path(root: &Path) -> PathBuf {
+    root.join(STATE_FILE)
+}
+
+pub fn progress_name(progress: Progress) -> &'static str {
+    match progress {
+        Progress::None => "none",
+        Progress::Some => "some",
+        Progress::Complete => "complete",
+    }
+}
+
+pub fn parse_progress(name: &str) -> Option<Progress> {
+    match name {
+        "none" => Some(Progress::None),
+        "some" => Some(Progress::Some),
+        "complete" => Some(Progress::Complete),
+        _ => None,
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct StateError {
+    // 1-based.
+    pub line: usize,
+    pub content: String,
+}
+
+impl fmt::Display for StateError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{STATE_FILE} line {}: expected `<exercise> <none|some|complete>`, found `{}`",
+            self.line, self.content,
+        )
+    }
+}
+
+impl Error for StateError {}
+
+pub fn parse_state(text: &str) -> Result<HashMap<String, Progress>, StateError> {
+    let mut state = HashMap::new();
+    for (index, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let mut fields = line.split_whitespace();
+        let entry = match (fields.next(), fields.next(), fields.next()) {
+            (Some(exercise), Some(progress), None) => {
+                parse_progress(progress).map(|progress| (exercise.to_string(), progress))
+            }
+            _ => None,
+        };
+        let (exercise, progress) = entry.ok_or_else(|| StateError {
+            line: index + 1,
+            content: line.to_string(),
+        })?;
+        state.insert(exercise, progress);
+    }
+    Ok(state)
+}
+
+// Sorted by exercise name to keep the file stable.
+pub fn render_state(state: &HashMap<String, Progress>) -> String {
+    let mut lines: Vec<String> = state
+        .iter()
+        .map(|(exercise, progress)| format!("{exercise} {}\n", progress_name(*progress)))
+        .collect();
+    lines.sort();
+    lines.concat()
+}
+
+// A missing file is an empty state.
+pub fn load_state(path: &Path) -> Result<HashMap<String, Progress>, Box<dyn Error>> {
+    match fs::read_to_string(path) {
+        Ok(text) => Ok(parse_state(&text)?),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(HashMap::new()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+pub fn save_state(path: &Path, state: &HashMap<String, Progress>) -> io::Result<()> {
+    fs::write(path, render_state(state))
+}
+
+// The done exercises in the state of the rustlings CLI:
+//
+// ```
+// DON'T EDIT THIS FILE!
+//
+// <current exercise>
+//
+// <done exercise>
+// …
+// ```
+//
+// Returns `None` if the header is missing.
+pub fn parse_rustlings_state(text: &str) -> Option<Vec<&str>> {
+    let mut lines = text.lines();
+    if lines.next()?.trim_end() != RUSTLINGS_STATE_HEADER {
+        return None;
+    }
+
+    // Skip the current exercise and the blank lines around it.
+    Some(
+        lines
+            .skip(3)
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect(),
+    )
+}
+
+// The done exercises of the rustlings CLI as complete. A missing file is an
+// empty seed.
+pub fn load_seed(root: &Path) -> Result<HashMap<String, Progress>, Box<dyn Error>> {
+    match fs::read_to_string(root.join(RUSTLINGS_STATE_FILE)) {
+        Ok(text) => Ok(parse_rustlings_state(&text)
+            .ok_or_else(|| format!("{RUSTLINGS_STATE_FILE}: missing `{RUSTLINGS_STATE_HEADER}`"))?
+            .into_iter()
+            .map(|exercise| (exercise.to_string(), Progress::Complete))
+            .collect()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(HashMap::new()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+// `state` on top of `seed`. Only for reading: saving the result would copy the
+// seed into `STATE_FILE` and hide later changes made in the rustlings CLI.
+pub fn seeded(
+    seed: &HashMap<String, Progress>,
+    state: &HashMap<String, Progress>,
+) -> HashMap<String, Progress> {
+    let mut seeded = seed.clone();
+    seeded.extend(
+        state
+            .iter()
+            .map(|(exercise, progress)| (exercise.clone(), *progress)),
+    );
+    seeded
+}
+
+pub fn load_seeded_state(root: &Path) -> Result<HashMap<String, Progress>, Box<dyn Error>> {
+    Ok(seeded(&load_seed(root)?, &load_state(&state_path(root))?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_and_render() {
+        let text = "if1 complete\n\nvariables1 some\nfrom_str none\n";
+        let state = parse_state(text).unwrap();
+        assert_eq!(state.len(), 3);
+        assert_eq!(state["if1"], Progress::Complete);
+        assert_eq!(state["variables1"], Progress::Some);
+        assert_eq!(state["from_str"], Progress::None);
+
+        assert_eq!(
+            render_state(&state),
+            "from_str none\nif1 complete\nvariables1 some\n",
+        );
+        assert_eq!(render_state(&HashMap::new()), "");
+    }
+
+    #[test]
+    fn invalid_lines() {
+        let error = |line, content: &str| {
+            Err(StateError {
+                line,
+                content: content.to_string(),
+            })
+        };
+        assert_eq!(parse_state("if1 done"), error(1, "if1 done"));
+        assert_eq!(parse_state("if1\nif2 some"), error(1, "if1"));
+        assert_eq!(
+            parse_state("if1 some\nif2 some extra"),
+            error(2, "if2 some extra"),
+        );
+    }
+
+    #[test]
+    fn load_missing_file() {
+        let state = load_state(Path::new("/nonexistent/progress.txt")).unwrap();
+        assert!(state.is_empty());
+        let seed = load_seed(Path::new("/nonexistent")).unwrap();
+        assert!(seed.is_empty());
+    }
+
+    #[test]
+    fn rustlings_state() {
+        let text = "DON'T EDIT THIS FILE!\n\nas_ref_mut\n\nintro1\nintro2\n";
+        assert_eq!(parse_rustlings_state(text), Some(vec!["intro1", "intro2"]));
+        assert_eq!(
+            parse_rustlings_state("DON'T EDIT THIS FILE!\n\nintro1\n"),
+            Some(vec![]),
+        );
+        assert_eq!(parse_rustlings_state("intro1 complete\n"), None);
+        assert_eq!(parse_rustlings_state(""), None);
+    }
+
+    #[test]
+    fn seeded_state() {
+        let seed = parse_rustlings_state("DON'T EDIT THIS FILE!\n\nif2\n\nintro1\nif1\n")
+            .unwrap()
+            .into_iter()
+            .map(|exercise| (exercise.to_string(), Progress::Complete))
+            .collect();
+        let state = parse_state("if1 some\nif2 some\n").unwrap();
+
+        let seeded = seeded(&seed, &state);
+        assert_eq!(seeded.len(), 3);
+        assert_eq!(seeded["intro1"], Progress::Complete);
+        // `STATE_FILE` takes precedence.
+        assert_eq!(seeded["if1"], Progress::Some);
+        assert_eq!(seeded["if2"], Progress::Some);
+    }
+
+    #[test]
+    fn same_model_as_iterators5() {
+        let solution = include_str!("../solutions/18_iterators/iterators5.rs");
+        // `progress_name` matches all variants, so there are no others.
+        let variants = [Progress::None, Progress::Some, Progress::Complete]
+            .map(|progress| format!("    {progress:?},\n"))
+            .concat();
+        assert!(solution.contains(&format!("enum Progress {{\n{variants}}}")));
+        assert!(solution.contains("    map.values().filter(|val| **val == value).count()\n"));
+        assert!(solution.contains(
+            "
+    collection
+        .iter()
+        .map(|map| count_iterator(map, value))
+        .sum()
+"
+        ));
+    }
+
+    #[test]
+    fn counts() {
+        let state = parse_state("if1 complete\nif2 some\nif3 complete").unwrap();
+        assert_eq!(count_iterator(&state, Progress::Complete), 2);
+        assert_eq!(count_iterator(&state, Progress::None), 0);
+
+        let other = parse_state("vecs1 complete").unwrap();
+        let collection = [state, other, HashMap::new()];
+        assert_eq!(
+            count_collection_iterator(&collection, Progress::Complete),
+            3
+        );
+        assert_eq!(count_collection_iterator(&collection, Progress::Some), 1);
+    }
+}